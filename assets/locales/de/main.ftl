@@ -0,0 +1,59 @@
+## Splash screen
+
+splash-load-error =
+    { $count ->
+        [one] { $files } konnte nicht geladen werden
+       *[other] { $count } Dateien konnten nicht geladen werden: { $files }
+    }
+splash-continue = Weiter
+
+## Intro slides
+
+slides-previous = Zurück
+slides-next = Weiter
+slides-skip = Überspringen
+intro-welcome = Willkommen bei Konnektoren, dem spielerischen Weg zu perfekter deutscher Grammatik.
+intro-why = Deutsche Grammatik ist schwer, und viele Lernende fallen durch die Prüfung.
+intro-badges = Erreiche mehr als 90 % in einer Herausforderung, um ein Abzeichen zu erhalten.
+intro-certificate = Zu jeder abgeschlossenen Herausforderung gibt es ein Zertifikat zum Teilen.
+intro-leaderboard = Vergleiche deine Ergebnisse mit anderen in der Bestenliste.
+intro-ready = Bist du bereit? Los geht's!
+
+## Map
+
+map-replay-intro = Intro wiederholen
+map-theme-light = Hell
+map-theme-dark = Dunkel
+map-colors-typical = Farben: Standard
+map-colors-red-green = Farben: rot-grün-sicher
+map-colors-blue-yellow = Farben: blau-gelb-sicher
+map-contrast-high = Hoher Kontrast
+map-contrast-normal = Normaler Kontrast
+map-text-size = Textgröße: { $percent } %
+map-zoom = Zoom: { $percent } %
+map-motion-reduced = Weniger Bewegung
+map-motion-full = Volle Bewegung
+map-challenge-details = { $name }: { $description }
+
+## Challenge
+
+game-task = Aufgabe: { $number }
+game-question =
+    Frage: { $question }
+
+    { $help }
+game-calculating-score = Punktzahl wird berechnet...
+game-score = Du hast die Herausforderung mit { $score } Punkten abgeschlossen!
+game-result-correct = Richtig: { $question }
+game-result-incorrect = Falsch: { $question }
+
+## Errors
+
+error-title = Etwas ist schiefgelaufen
+error-challenge = Diese Herausforderung konnte nicht gestartet werden.
+error-content = Ein Teil dieser Herausforderung fehlt.
+error-unknown = Im Spiel ist ein Problem aufgetreten.
+error-show-details = Details anzeigen
+error-hide-details = Details ausblenden
+error-return-to-map = Zurück zur Karte
+error-return-to-intro = Zurück zur Einführung
@@ -0,0 +1,59 @@
+## Splash screen
+
+splash-load-error =
+    { $count ->
+        [one] Could not load { $files }
+       *[other] Could not load { $count } files: { $files }
+    }
+splash-continue = Continue
+
+## Intro slides
+
+slides-previous = Previous
+slides-next = Next
+slides-skip = Skip
+intro-welcome = Welcome to Konnektoren, the gamified way to perfect your German grammar.
+intro-why = German grammar is hard, and many learners fail the exam.
+intro-badges = Score more than 90% in a challenge to earn a badge.
+intro-certificate = Every completed challenge comes with a certificate you can share.
+intro-leaderboard = Compare your results with other players on the leaderboard.
+intro-ready = Are you ready? Let's get started!
+
+## Map
+
+map-replay-intro = Replay intro
+map-theme-light = Light
+map-theme-dark = Dark
+map-colors-typical = Colours: standard
+map-colors-red-green = Colours: red-green safe
+map-colors-blue-yellow = Colours: blue-yellow safe
+map-contrast-high = High contrast
+map-contrast-normal = Normal contrast
+map-text-size = Text size: { $percent }%
+map-zoom = Zoom: { $percent }%
+map-motion-reduced = Reduce motion
+map-motion-full = Full motion
+map-challenge-details = { $name }: { $description }
+
+## Challenge
+
+game-task = Task: { $number }
+game-question =
+    Question: { $question }
+
+    { $help }
+game-calculating-score = Calculating score...
+game-score = You have completed the challenge with a score of { $score }!
+game-result-correct = Correct: { $question }
+game-result-incorrect = Incorrect: { $question }
+
+## Errors
+
+error-title = Something went wrong
+error-challenge = This challenge could not be started.
+error-content = Part of this challenge is missing.
+error-unknown = The game ran into a problem.
+error-show-details = Show details
+error-hide-details = Hide details
+error-return-to-map = Return to map
+error-return-to-intro = Return to intro
@@ -0,0 +1,59 @@
+## Splash screen
+
+splash-load-error =
+    { $count ->
+        [one] Не вдалося завантажити { $files }
+       *[other] Не вдалося завантажити файли ({ $count }): { $files }
+    }
+splash-continue = Продовжити
+
+## Intro slides
+
+slides-previous = Назад
+slides-next = Далі
+slides-skip = Пропустити
+intro-welcome = Ласкаво просимо до Konnektoren — ігрового шляху до досконалої німецької граматики.
+intro-why = Німецька граматика складна, і багато хто не складає іспит.
+intro-badges = Наберіть понад 90 % у завданні, щоб отримати значок.
+intro-certificate = За кожне виконане завдання ви отримуєте сертифікат, яким можна поділитися.
+intro-leaderboard = Порівнюйте свої результати з іншими гравцями в таблиці лідерів.
+intro-ready = Готові? Почнімо!
+
+## Map
+
+map-replay-intro = Переглянути вступ
+map-theme-light = Світла
+map-theme-dark = Темна
+map-colors-typical = Кольори: стандартні
+map-colors-red-green = Кольори: для червоно-зеленої сліпоти
+map-colors-blue-yellow = Кольори: для синьо-жовтої сліпоти
+map-contrast-high = Висока контрастність
+map-contrast-normal = Звичайна контрастність
+map-text-size = Розмір тексту: { $percent }%
+map-zoom = Масштаб: { $percent }%
+map-motion-reduced = Менше руху
+map-motion-full = Повний рух
+map-challenge-details = { $name }: { $description }
+
+## Challenge
+
+game-task = Завдання: { $number }
+game-question =
+    Питання: { $question }
+
+    { $help }
+game-calculating-score = Обчислення результату...
+game-score = Ви завершили завдання з результатом { $score }!
+game-result-correct = Правильно: { $question }
+game-result-incorrect = Неправильно: { $question }
+
+## Errors
+
+error-title = Щось пішло не так
+error-challenge = Не вдалося розпочати це завдання.
+error-content = Частина цього завдання відсутня.
+error-unknown = У грі виникла проблема.
+error-show-details = Показати подробиці
+error-hide-details = Сховати подробиці
+error-return-to-map = Повернутися до карти
+error-return-to-intro = Повернутися до вступу
@@ -0,0 +1,247 @@
+use bevy::{
+    a11y::{
+        accesskit::{Live, NodeBuilder, Role},
+        AccessibilityNode, Focus,
+    },
+    prelude::*,
+    utils::HashSet,
+};
+
+use crate::{
+    button::{ButtonLabel, Disabled, Focused},
+    i18n::{LocalizeText, LocalizedText},
+    theme::{FontSize, ThemeColor, ThemedText, UiTheme},
+};
+
+/// Text whose changes screen readers read out without moving focus, e.g. the question.
+#[derive(Component)]
+pub struct LiveRegion;
+
+/// Message read out by screen readers, e.g. whether an answer was correct.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct Announcement(pub LocalizedText);
+
+/// Hidden live region holding the latest [`Announcement`].
+#[derive(Component)]
+struct Announcer;
+
+pub fn accessibility_plugin(app: &mut App) {
+    app.add_event::<Announcement>()
+        .add_systems(Startup, spawn_announcer)
+        // After the localisation, which fills in labels after the buttons spawn.
+        .add_systems(
+            PostUpdate,
+            (
+                announce.before(LocalizeText),
+                (describe_buttons, describe_live_regions, sync_focus).after(LocalizeText),
+            ),
+        );
+
+    #[cfg(target_arch = "wasm32")]
+    app.add_systems(PostUpdate, web::mirror_to_dom);
+}
+
+fn spawn_announcer(mut commands: Commands, theme: Res<UiTheme>) {
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section("", theme.text_style(FontSize::Medium)),
+            style: Style {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        ThemedText(ThemeColor::Text, FontSize::Medium),
+        LocalizedText::new(""),
+        LiveRegion,
+        Announcer,
+    ));
+}
+
+fn announce(
+    mut announcements: EventReader<Announcement>,
+    mut announcers: Query<&mut LocalizedText, With<Announcer>>,
+) {
+    let Some(Announcement(text)) = announcements.read().last() else {
+        return;
+    };
+    for mut announcer in announcers.iter_mut() {
+        *announcer = text.clone();
+    }
+}
+
+fn text_value(text: &Text) -> String {
+    text.sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
+/// Updates `node` only where it differs, so AccessKit is not sent unchanged trees.
+fn describe(
+    commands: &mut Commands,
+    entity: Entity,
+    node: Option<Mut<AccessibilityNode>>,
+    role: Role,
+    name: &str,
+    configure: impl Fn(&mut NodeBuilder),
+) {
+    let mut expected = NodeBuilder::new(role);
+    expected.set_name(name);
+    configure(&mut expected);
+    match node {
+        Some(mut node) => {
+            if node.role() != role
+                || node.name() != Some(name)
+                || node.is_disabled() != expected.is_disabled()
+                || node.live() != expected.live()
+            {
+                node.0 = expected;
+            }
+        }
+        None => {
+            commands
+                .entity(entity)
+                .insert(AccessibilityNode::from(expected));
+        }
+    }
+}
+
+/// Buttons with their label and accessibility node, if they have one yet.
+type DescribedButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Children,
+        Has<Disabled>,
+        Option<&'static mut AccessibilityNode>,
+    ),
+    With<Button>,
+>;
+
+/// Names every button after its label and marks disabled ones, for the buttons that were
+/// added, relabelled, disabled or enabled.
+fn describe_buttons(
+    mut commands: Commands,
+    mut buttons: DescribedButtons,
+    labels: Query<&Text, With<ButtonLabel>>,
+    relabelled: Query<&Parent, (With<ButtonLabel>, Changed<Text>)>,
+    added: Query<Entity, Added<Button>>,
+    disabled: Query<Entity, Added<Disabled>>,
+    mut enabled: RemovedComponents<Disabled>,
+) {
+    let changed: HashSet<Entity> = relabelled
+        .iter()
+        .map(Parent::get)
+        .chain(added.iter())
+        .chain(disabled.iter())
+        .chain(enabled.read())
+        .collect();
+    let mut changed_buttons = buttons.iter_many_mut(&changed);
+    while let Some((entity, children, disabled, node)) = changed_buttons.fetch_next() {
+        let name = labels
+            .iter_many(children)
+            .map(text_value)
+            .collect::<Vec<_>>()
+            .join(" ");
+        describe(&mut commands, entity, node, Role::Button, &name, |node| {
+            if disabled {
+                node.set_disabled();
+            }
+        });
+    }
+}
+
+/// Live regions that are new or whose text changed, with their accessibility node if they
+/// have one yet.
+type ChangedLiveRegions<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Text,
+        Option<&'static mut AccessibilityNode>,
+    ),
+    (With<LiveRegion>, Or<(Changed<Text>, Added<LiveRegion>)>),
+>;
+
+fn describe_live_regions(mut commands: Commands, mut regions: ChangedLiveRegions) {
+    for (entity, text, node) in regions.iter_mut() {
+        let name = text_value(text);
+        describe(
+            &mut commands,
+            entity,
+            node,
+            Role::StaticText,
+            &name,
+            |node| {
+                node.set_live(Live::Polite);
+            },
+        );
+    }
+}
+
+/// Hands keyboard focus to the screen reader, which only exists with a window.
+fn sync_focus(focused: Query<Entity, Added<Focused>>, focus: Option<ResMut<Focus>>) {
+    let (Some(entity), Some(mut focus)) = (focused.iter().last(), focus) else {
+        return;
+    };
+    if focus.0 != Some(entity) {
+        focus.0 = Some(entity);
+    }
+}
+
+/// AccessKit has no web adapter, so the browser build reads out live regions and focused
+/// buttons through a visually hidden ARIA live region in the page instead.
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use bevy::prelude::*;
+
+    use super::{text_value, LiveRegion};
+    use crate::button::{ButtonLabel, Focused};
+
+    const LIVE_ELEMENT_ID: &str = "konnektoren-live";
+
+    fn live_element() -> Option<web_sys::Element> {
+        let document = web_sys::window()?.document()?;
+        if let Some(element) = document.get_element_by_id(LIVE_ELEMENT_ID) {
+            return Some(element);
+        }
+        let element = document.create_element("div").ok()?;
+        element.set_id(LIVE_ELEMENT_ID);
+        element.set_attribute("role", "status").ok()?;
+        element.set_attribute("aria-live", "polite").ok()?;
+        element
+            .set_attribute(
+                "style",
+                "position:absolute;width:1px;height:1px;overflow:hidden;clip:rect(0 0 0 0);white-space:nowrap",
+            )
+            .ok()?;
+        document.body()?.append_child(&element).ok()?;
+        Some(element)
+    }
+
+    pub(super) fn mirror_to_dom(
+        regions: Query<&Text, (With<LiveRegion>, Changed<Text>)>,
+        focused: Query<&Children, Added<Focused>>,
+        labels: Query<&Text, With<ButtonLabel>>,
+    ) {
+        let focused_names = focused
+            .iter()
+            .flat_map(|children| labels.iter_many(children));
+        let Some(message) = regions
+            .iter()
+            .chain(focused_names)
+            .map(text_value)
+            .filter(|message| !message.is_empty())
+            .last()
+        else {
+            return;
+        };
+        if let Some(element) = live_element() {
+            element.set_text_content(Some(&message));
+        }
+    }
+}
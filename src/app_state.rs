@@ -7,4 +7,6 @@ pub enum AppState {
     Slides,
     Game,
     Map,
+    /// Shown after an error the player cannot go on from.
+    Error,
 }
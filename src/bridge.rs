@@ -0,0 +1,280 @@
+use bevy::{log, prelude::*};
+use konnektoren_core::{challenges::ChallengeType, game::GamePath};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::events::{ChallengeCompleted, ChallengeStarted, GameAction},
+    game_state::GameState,
+    settings::Settings,
+};
+
+/// Name of the DOM event the host page sends commands with.
+pub const COMMAND_EVENT: &str = "konnektoren:command";
+
+/// Answer given to a task of a completed challenge.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub question: String,
+    pub correct: bool,
+}
+
+/// Progress reported to the page hosting the web build, as a DOM `CustomEvent` on `window`
+/// with the fields as its JSON `detail`.
+#[derive(Event, Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum HostEvent {
+    #[serde(rename_all = "camelCase")]
+    ChallengeStarted { challenge_id: String },
+    #[serde(rename_all = "camelCase")]
+    ChallengeCompleted {
+        challenge_id: String,
+        /// Percentage of correctly answered tasks.
+        score: f32,
+        outcomes: Vec<Outcome>,
+    },
+}
+
+impl HostEvent {
+    /// Type of the DOM event, for `addEventListener`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ChallengeStarted { .. } => "konnektoren:challenge-started",
+            Self::ChallengeCompleted { .. } => "konnektoren:challenge-completed",
+        }
+    }
+
+    pub fn detail(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Command from the page hosting the web build, sent as the `detail` of a
+/// [`COMMAND_EVENT`], e.g. `{"command": "start-challenge", "challenge": "konnektoren-1"}`.
+#[derive(Event, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum HostCommand {
+    StartChallenge {
+        challenge: String,
+    },
+    /// Replaces the learning path, and with it the challenges on the map.
+    LoadContent {
+        content: GamePath,
+    },
+    SetLanguage {
+        language: String,
+    },
+}
+
+impl HostCommand {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Lets a page embedding the web build follow the player's progress and control the game.
+/// Other builds have no page, so nothing is sent to or received from one.
+pub fn bridge_plugin(app: &mut App) {
+    app.add_event::<HostEvent>()
+        .add_event::<HostCommand>()
+        .add_systems(
+            Update,
+            (
+                report_progress,
+                execute_host_commands.run_if(on_event::<HostCommand>()),
+            ),
+        );
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        app.add_systems(Startup, web::listen_for_commands)
+            .add_systems(PreUpdate, web::receive_commands)
+            .add_systems(
+                PostUpdate,
+                web::dispatch_events.run_if(on_event::<HostEvent>()),
+            );
+    }
+}
+
+fn outcomes(game_state: &GameState) -> Vec<Outcome> {
+    match game_state.challenge.challenge_type {
+        ChallengeType::MultipleChoice(ref dataset) => dataset
+            .questions
+            .iter()
+            .zip(game_state.answers())
+            .map(|(question, correct)| Outcome {
+                question: question.question.clone(),
+                correct,
+            })
+            .collect(),
+    }
+}
+
+fn report_progress(
+    mut started: EventReader<ChallengeStarted>,
+    mut completed: EventReader<ChallengeCompleted>,
+    game_state: Res<GameState>,
+    mut host_events: EventWriter<HostEvent>,
+) {
+    for event in started.read() {
+        host_events.send(HostEvent::ChallengeStarted {
+            challenge_id: event.challenge_id.clone(),
+        });
+    }
+    for event in completed.read() {
+        host_events.send(HostEvent::ChallengeCompleted {
+            challenge_id: event.challenge_id.clone(),
+            score: event.score,
+            outcomes: outcomes(&game_state),
+        });
+    }
+}
+
+/// Content loaded while the map is shown appears the next time the map is entered.
+fn execute_host_commands(
+    mut commands: EventReader<HostCommand>,
+    mut game_state: ResMut<GameState>,
+    mut settings: ResMut<Settings>,
+    mut actions: EventWriter<GameAction>,
+) {
+    for command in commands.read() {
+        match command {
+            HostCommand::StartChallenge { challenge } => {
+                actions.send(GameAction::StartChallenge(challenge.clone()));
+            }
+            HostCommand::LoadContent { content } => {
+                log::info!("Loaded learning path {}", content.id);
+                game_state.game.game_path = content.clone();
+            }
+            HostCommand::SetLanguage { language } => {
+                if settings.language != *language {
+                    settings.language = language.clone();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::sync::{Arc, Mutex};
+
+    use bevy::{log, prelude::*, window::RequestRedraw, winit::EventLoopProxy};
+    use web_sys::{
+        js_sys::JSON,
+        wasm_bindgen::{closure::Closure, JsCast},
+        CustomEvent, CustomEventInit,
+    };
+
+    use super::{HostCommand, HostEvent, COMMAND_EVENT};
+
+    /// Commands received by the DOM listener since the last frame.
+    #[derive(Resource, Clone, Default)]
+    pub(super) struct ReceivedCommands(Arc<Mutex<Vec<HostCommand>>>);
+
+    pub(super) fn listen_for_commands(
+        mut commands: Commands,
+        proxy: Option<NonSend<EventLoopProxy>>,
+    ) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let received = ReceivedCommands::default();
+        let queue = received.clone();
+        // The event loop sleeps while nothing happens, so a command from the
+        // page has to wake it up to be handled before the next input.
+        let proxy = proxy.map(|proxy| proxy.clone());
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            let Some(event) = event.dyn_ref::<CustomEvent>() else {
+                return;
+            };
+            let detail = event.detail();
+            let json = detail
+                .as_string()
+                .or_else(|| {
+                    JSON::stringify(&detail)
+                        .ok()
+                        .and_then(|json| json.as_string())
+                })
+                .unwrap_or_default();
+            match HostCommand::from_json(&json) {
+                Ok(command) => {
+                    queue.0.lock().unwrap().push(command);
+                    if let Some(proxy) = &proxy {
+                        let _ = proxy.send_event(RequestRedraw);
+                    }
+                }
+                Err(e) => log::warn!("Ignoring command {}: {}", json, e),
+            }
+        });
+        if window
+            .add_event_listener_with_callback(COMMAND_EVENT, listener.as_ref().unchecked_ref())
+            .is_ok()
+        {
+            // The listener lives as long as the page.
+            listener.forget();
+            commands.insert_resource(received);
+        }
+    }
+
+    pub(super) fn receive_commands(
+        received: Option<Res<ReceivedCommands>>,
+        mut commands: EventWriter<HostCommand>,
+    ) {
+        let Some(received) = received else {
+            return;
+        };
+        let Ok(mut queue) = received.0.lock() else {
+            return;
+        };
+        commands.send_batch(queue.drain(..));
+    }
+
+    pub(super) fn dispatch_events(mut events: EventReader<HostEvent>) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        for event in events.read() {
+            let Ok(detail) = JSON::parse(&event.detail()) else {
+                continue;
+            };
+            let mut init = CustomEventInit::new();
+            init.detail(&detail);
+            match CustomEvent::new_with_event_init_dict(event.name(), &init) {
+                Ok(dom_event) => {
+                    let _ = window.dispatch_event(&dom_event);
+                }
+                Err(e) => log::warn!("Error creating {}: {:?}", event.name(), e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_commands_and_writes_events() {
+        assert_eq!(
+            HostCommand::from_json(r#"{"command": "set-language", "language": "de"}"#).unwrap(),
+            HostCommand::SetLanguage {
+                language: "de".to_string()
+            }
+        );
+        assert!(HostCommand::from_json(r#"{"command": "jump"}"#).is_err());
+
+        let completed = HostEvent::ChallengeCompleted {
+            challenge_id: "konnektoren-1".to_string(),
+            score: 50.0,
+            outcomes: vec![Outcome {
+                question: "und".to_string(),
+                correct: true,
+            }],
+        };
+        assert_eq!(completed.name(), "konnektoren:challenge-completed");
+        assert_eq!(
+            completed.detail(),
+            r#"{"challengeId":"konnektoren-1","score":50.0,"outcomes":[{"question":"und","correct":true}]}"#
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+
+use crate::{
+    i18n::{Localization, LocalizedText},
+    theme::{ButtonColors, FontSize, ThemeColor, ThemedText, UiTheme},
+    touch::Pointer,
+};
+
+/// Colour set a button is drawn with, looked up in the active palette.
+#[derive(Component, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum ButtonKind {
+    #[default]
+    Standard,
+    MapNode,
+}
+
+/// Marks the button that currently has keyboard focus.
+#[derive(Component)]
+pub struct Focused;
+
+/// Marks a button that ignores presses and is drawn greyed out.
+#[derive(Component)]
+pub struct Disabled;
+
+/// Marks the text child holding a button's label.
+#[derive(Component)]
+pub struct ButtonLabel;
+
+/// Buttons carrying the action component `A` whose interaction changed this frame.
+pub type ButtonPresses<'w, 's, A> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static A),
+    (Changed<Interaction>, With<Button>, Without<Disabled>),
+>;
+
+/// Keyboard navigation between buttons, for ordering systems that take keys before it.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyboardFocus;
+
+/// Iterates the actions of all buttons that were just pressed.
+pub fn pressed<'a, A: Component>(presses: &'a ButtonPresses<A>) -> impl Iterator<Item = &'a A> {
+    presses
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, action)| action)
+}
+
+/// Replaces the label of the buttons in `toggles`, e.g. after a toggle switched what it
+/// names.
+pub fn set_toggle_label<A: Component>(
+    toggles: &Query<&Children, With<A>>,
+    labels: &mut Query<&mut LocalizedText, With<ButtonLabel>>,
+    label: LocalizedText,
+) {
+    for children in toggles.iter() {
+        let mut texts = labels.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            *text = label.clone();
+        }
+    }
+}
+
+/// Content and layout of a button spawned by [`spawn_button`].
+pub struct ButtonSpec {
+    /// Label shown as is, e.g. content that is not translated.
+    pub label: Option<String>,
+    /// Label in the active language; takes precedence over `label`.
+    pub localized: Option<LocalizedText>,
+    pub icon: Option<Handle<Image>>,
+    pub kind: ButtonKind,
+    pub style: Option<Style>,
+    pub font_size: Option<FontSize>,
+}
+
+impl ButtonSpec {
+    pub fn label(label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            localized: None,
+            icon: None,
+            kind: ButtonKind::default(),
+            style: None,
+            font_size: None,
+        }
+    }
+
+    pub fn icon(icon: Handle<Image>) -> Self {
+        Self {
+            label: None,
+            localized: None,
+            icon: Some(icon),
+            kind: ButtonKind::default(),
+            style: None,
+            font_size: None,
+        }
+    }
+
+    pub fn localized(text: LocalizedText) -> Self {
+        Self {
+            label: None,
+            localized: Some(text),
+            icon: None,
+            kind: ButtonKind::default(),
+            style: None,
+            font_size: None,
+        }
+    }
+
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_kind(mut self, kind: ButtonKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Replaces the button layout, usually built on top of [`UiTheme::button_style`].
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: FontSize) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+}
+
+/// Spawns a themed button carrying `action`, typically its typed action component.
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    spec: ButtonSpec,
+    action: impl Bundle,
+) -> Entity {
+    let style = spec.style.unwrap_or_else(|| theme.button_style());
+    let font_size = spec.font_size.unwrap_or(FontSize::Large);
+    let colors = button_colors(theme, spec.kind);
+
+    parent
+        .spawn((
+            ButtonBundle {
+                style,
+                background_color: colors.normal.into(),
+                border_color: theme.palette.outline.into(),
+                ..default()
+            },
+            spec.kind,
+            action,
+        ))
+        .with_children(|button| {
+            if let Some(icon) = spec.icon {
+                button.spawn(ImageBundle {
+                    style: Style {
+                        width: Val::Px(theme.font_sizes.get(font_size) * 1.2),
+                        height: Val::Px(theme.font_sizes.get(font_size) * 1.2),
+                        ..default()
+                    },
+                    image: UiImage::new(icon),
+                    ..default()
+                });
+            }
+            if let Some(text) = spec.localized {
+                button.spawn((
+                    TextBundle::from_section("", theme.button_text_style(font_size)),
+                    ThemedText(ThemeColor::ButtonText, font_size),
+                    ButtonLabel,
+                    text,
+                ));
+            } else if let Some(label) = spec.label {
+                button.spawn((
+                    TextBundle::from_section(label, theme.button_text_style(font_size)),
+                    ThemedText(ThemeColor::ButtonText, font_size),
+                    ButtonLabel,
+                ));
+            }
+        })
+        .id()
+}
+
+pub fn button_plugin(app: &mut App) {
+    app.add_systems(
+        PreUpdate,
+        // After the pointer, so a keyboard press is not overwritten before screens see it.
+        keyboard_focus
+            .in_set(KeyboardFocus)
+            .after(InputSystem)
+            .after(UiSystem::Focus),
+    )
+    .add_systems(Update, update_button_visuals);
+}
+
+/// Orders `buttons` the way they are read: rows from top to bottom, and within a row from
+/// left to right, or from right to left for right-to-left languages.
+fn reading_order(mut buttons: Vec<(Entity, Rect)>, right_to_left: bool) -> Vec<Entity> {
+    // Stable sorts, so buttons without a layout yet keep the order they were spawned in.
+    buttons.sort_by_key(|(entity, _)| *entity);
+    buttons.sort_by(|(_, a), (_, b)| a.center().y.total_cmp(&b.center().y));
+
+    let mut rows: Vec<Vec<(Entity, Rect)>> = Vec::new();
+    for button in buttons {
+        match rows.last_mut() {
+            // A button belongs to the row if it overlaps the vertical centre of its first.
+            Some(row) if button.1.min.y <= row[0].1.center().y => row.push(button),
+            _ => rows.push(vec![button]),
+        }
+    }
+
+    rows.into_iter()
+        .flat_map(|mut row| {
+            row.sort_by(|(_, a), (_, b)| {
+                let order = a.center().x.total_cmp(&b.center().x);
+                if right_to_left {
+                    order.reverse()
+                } else {
+                    order
+                }
+            });
+            row.into_iter().map(|(entity, _)| entity)
+        })
+        .collect()
+}
+
+/// Buttons that can take the keyboard focus, with the layout their reading order comes from.
+type FocusableButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Node,
+        &'static GlobalTransform,
+        Has<Focused>,
+    ),
+    (With<Button>, Without<Disabled>),
+>;
+
+/// Moves focus between buttons in reading order with Tab and Shift+Tab, and presses the
+/// focused button with Enter.
+fn keyboard_focus(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    localization: Res<Localization>,
+    buttons: FocusableButtons,
+    mut interactions: Query<&mut Interaction>,
+    mut pressed_by_key: Local<Option<Entity>>,
+) {
+    // Release the button pressed last frame, as the pointer would.
+    if let Some(entity) = pressed_by_key.take() {
+        if let Ok(mut interaction) = interactions.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+
+    let order = reading_order(
+        buttons
+            .iter()
+            .map(|(entity, node, transform, _)| (entity, node.logical_rect(transform)))
+            .collect(),
+        localization.is_right_to_left(),
+    );
+    let focused = buttons
+        .iter()
+        .find(|(_, _, _, focused)| *focused)
+        .and_then(|(entity, ..)| order.iter().position(|button| *button == entity));
+
+    if keys.just_pressed(KeyCode::Tab) && !order.is_empty() {
+        let backwards = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let next = match (focused, backwards) {
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+            (Some(index), false) => (index + 1) % order.len(),
+            (Some(index), true) => (index + order.len() - 1) % order.len(),
+        };
+        if let Some(index) = focused {
+            commands.entity(order[index]).remove::<Focused>();
+        }
+        commands.entity(order[next]).insert(Focused);
+    } else if keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        let Some(entity) = focused.map(|index| order[index]) else {
+            return;
+        };
+        if let Ok(mut interaction) = interactions.get_mut(entity) {
+            *interaction = Interaction::Pressed;
+            *pressed_by_key = Some(entity);
+        }
+    }
+}
+
+fn button_colors(theme: &UiTheme, kind: ButtonKind) -> &ButtonColors {
+    match kind {
+        ButtonKind::Standard => &theme.palette.button,
+        ButtonKind::MapNode => &theme.palette.map_node,
+    }
+}
+
+/// Buttons with the state their colours follow.
+type ButtonVisuals<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Interaction,
+        &'static ButtonKind,
+        Has<Focused>,
+        Has<Disabled>,
+        &'static mut BackgroundColor,
+        &'static mut BorderColor,
+    ),
+>;
+
+fn update_button_visuals(theme: Res<UiTheme>, pointer: Res<Pointer>, mut buttons: ButtonVisuals) {
+    for (interaction, kind, focused, disabled, mut background, mut border) in buttons.iter_mut() {
+        let colors = button_colors(&theme, *kind);
+        let background_color = match (disabled, interaction) {
+            (true, _) => colors.disabled,
+            (false, Interaction::Pressed) => colors.pressed,
+            // A finger lifted off a button leaves no hover worth showing.
+            (false, Interaction::Hovered) if *pointer == Pointer::Touch => colors.normal,
+            (false, Interaction::Hovered) => colors.hovered,
+            (false, Interaction::None) => colors.normal,
+        };
+        let border_color = if focused && !disabled {
+            theme.palette.focus
+        } else {
+            theme.palette.outline
+        };
+
+        if background.0 != background_color {
+            background.0 = background_color;
+        }
+        if border.0 != border_color {
+            border.0 = border_color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(index: u32, x: f32, y: f32) -> (Entity, Rect) {
+        (
+            Entity::from_raw(index),
+            Rect::from_center_size(Vec2::new(x, y), Vec2::new(80.0, 40.0)),
+        )
+    }
+
+    #[test]
+    fn focus_follows_rows_in_reading_direction() {
+        let buttons = vec![
+            button(0, 300.0, 500.0),
+            button(1, 100.0, 505.0),
+            button(2, 200.0, 100.0),
+            button(3, 100.0, 500.0),
+        ];
+        let ids = |order: Vec<Entity>| order.iter().map(|e| e.index()).collect::<Vec<_>>();
+
+        assert_eq!(ids(reading_order(buttons.clone(), false)), [2, 3, 1, 0]);
+        assert_eq!(ids(reading_order(buttons, true)), [2, 0, 3, 1]);
+    }
+}
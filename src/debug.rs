@@ -0,0 +1,279 @@
+use std::str::FromStr;
+
+use bevy::{
+    ecs::entity::Entities, input::InputSystem, prelude::*, ui::FocusPolicy,
+    window::ReceivedCharacter,
+};
+
+use crate::{
+    app_state::AppState,
+    button::KeyboardFocus,
+    game::events::GameAction,
+    game_state::GameState,
+    profile::Profile,
+    screen::TransitionTo,
+    theme::{FontSize, ThemeColor, ThemedBackground, ThemedText, UiTheme},
+};
+
+/// Keys that show and hide the debug overlay.
+const TOGGLE_KEYS: [KeyCode; 2] = [KeyCode::F12, KeyCode::Backquote];
+
+const HELP: &str = "Commands: goto map|intro|splash, start challenge <id>, answer correctly, \
+complete challenge, reset progress";
+
+/// Action of the debug console, e.g. `start challenge konnektoren-1`.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub enum DebugCommand {
+    Goto(AppState),
+    StartChallenge(String),
+    /// Answers the current task correctly.
+    AnswerCorrectly,
+    /// Answers the remaining tasks correctly.
+    CompleteChallenge,
+    ResetProgress,
+    Help,
+}
+
+impl FromStr for DebugCommand {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let command = match words[..] {
+            ["goto", "map"] => Self::Goto(AppState::Map),
+            ["goto", "intro" | "slides"] => Self::Goto(AppState::Slides),
+            ["goto", "splash"] => Self::Goto(AppState::Splash),
+            ["start", "challenge", id] | ["start", id] => Self::StartChallenge(id.to_string()),
+            ["answer", "correctly"] => Self::AnswerCorrectly,
+            ["complete", "challenge"] | ["complete"] => Self::CompleteChallenge,
+            ["reset", "progress"] => Self::ResetProgress,
+            ["help"] | ["?"] => Self::Help,
+            _ => return Err(format!("Unknown command: {}", line.trim())),
+        };
+        Ok(command)
+    }
+}
+
+/// State of the debug overlay and its command console.
+#[derive(Resource, Default, Debug)]
+pub struct DebugConsole {
+    pub open: bool,
+    /// Command being typed.
+    pub input: String,
+    /// Reply to the last command.
+    pub output: String,
+}
+
+#[derive(Component)]
+struct DebugOverlay;
+
+/// Current state of the game, refreshed while the overlay is open.
+#[derive(Component)]
+struct DebugInfo;
+
+#[derive(Component)]
+struct ConsoleText;
+
+/// Developer tools: an overlay with the state of the game and a console for shortcuts
+/// through it, shown with F12 or the backtick key.
+pub fn debug_plugin(app: &mut App) {
+    app.add_event::<DebugCommand>()
+        .init_resource::<DebugConsole>()
+        .add_systems(Startup, spawn_overlay)
+        // Before the screens and button focus, which would otherwise also act on the keys typed.
+        .add_systems(
+            PreUpdate,
+            console_input.after(InputSystem).before(KeyboardFocus),
+        )
+        .add_systems(
+            Update,
+            (
+                execute_debug_commands.run_if(on_event::<DebugCommand>()),
+                show_console.run_if(resource_changed::<DebugConsole>),
+                update_debug_info.run_if(|console: Res<DebugConsole>| console.open),
+            )
+                .chain(),
+        );
+}
+
+fn spawn_overlay(mut commands: Commands, theme: Res<UiTheme>) {
+    let text = || {
+        (
+            TextBundle::from_section("", theme.text_style(FontSize::Medium)),
+            ThemedText(ThemeColor::Text, FontSize::Medium),
+        )
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                background_color: theme.palette.color(ThemeColor::Surface).into(),
+                visibility: Visibility::Hidden,
+                focus_policy: FocusPolicy::Pass,
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            ThemedBackground(ThemeColor::Surface),
+            DebugOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((text(), DebugInfo));
+            parent.spawn((text(), ConsoleText));
+        });
+}
+
+/// Toggles the overlay and, while it is open, takes the keyboard for the console.
+fn console_input(
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<DebugConsole>,
+    mut debug_commands: EventWriter<DebugCommand>,
+) {
+    if keys.any_just_pressed(TOGGLE_KEYS) {
+        console.open = !console.open;
+        characters.clear();
+        keys.clear();
+        return;
+    }
+    if !console.open {
+        characters.clear();
+        return;
+    }
+    for character in characters.read() {
+        for c in character
+            .char
+            .chars()
+            .filter(|c| !c.is_control() && *c != '`')
+        {
+            console.input.push(c);
+        }
+    }
+    if keys.just_pressed(KeyCode::Backspace) {
+        console.input.pop();
+    }
+    if keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        let line = std::mem::take(&mut console.input);
+        match line.parse() {
+            Ok(command) => {
+                debug_commands.send(command);
+            }
+            Err(e) => console.output = e,
+        }
+    }
+    keys.clear();
+}
+
+fn execute_debug_commands(
+    mut debug_commands: EventReader<DebugCommand>,
+    game_state: Res<GameState>,
+    mut profile: ResMut<Profile>,
+    mut console: ResMut<DebugConsole>,
+    mut transitions: EventWriter<TransitionTo>,
+    mut actions: EventWriter<GameAction>,
+) {
+    for command in debug_commands.read() {
+        let in_progress = game_state.task_count() > 0 && !game_state.is_completed();
+        console.output = match command {
+            DebugCommand::Goto(state) => {
+                transitions.send(TransitionTo(*state));
+                format!("Going to {:?}", state)
+            }
+            DebugCommand::StartChallenge(id) => {
+                actions.send(GameAction::StartChallenge(id.clone()));
+                format!("Starting challenge {}", id)
+            }
+            DebugCommand::AnswerCorrectly | DebugCommand::CompleteChallenge if !in_progress => {
+                "No challenge in progress".to_string()
+            }
+            DebugCommand::AnswerCorrectly => {
+                let task = game_state.current_task_index;
+                actions.send_batch(game_state.correct_option(task).map(GameAction::SolveOption));
+                format!("Answered task {}", task + 1)
+            }
+            DebugCommand::CompleteChallenge => {
+                let tasks = game_state.current_task_index..game_state.task_count();
+                actions.send_batch(
+                    tasks
+                        .filter_map(|task| game_state.correct_option(task))
+                        .map(GameAction::SolveOption),
+                );
+                "Completed the challenge".to_string()
+            }
+            DebugCommand::ResetProgress => {
+                *profile = Profile::default();
+                "Progress reset".to_string()
+            }
+            DebugCommand::Help => HELP.to_string(),
+        };
+    }
+}
+
+fn show_console(
+    console: Res<DebugConsole>,
+    mut overlays: Query<&mut Visibility, With<DebugOverlay>>,
+    mut console_text: Query<&mut Text, With<ConsoleText>>,
+) {
+    let visibility = if console.open {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut overlay in &mut overlays {
+        overlay.set_if_neq(visibility);
+    }
+    for mut text in &mut console_text {
+        text.sections[0].value = format!("> {}_\n{}", console.input, console.output);
+    }
+}
+
+fn update_debug_info(
+    state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    time: Res<Time>,
+    entities: &Entities,
+    mut fps: Local<f32>,
+    mut info: Query<&mut Text, With<DebugInfo>>,
+) {
+    if time.delta_seconds() > 0.0 {
+        // Smoothed, so the number can be read.
+        *fps = *fps * 0.9 + 0.1 / time.delta_seconds();
+    }
+    for mut text in &mut info {
+        text.sections[0].value = format!(
+            "State: {:?}\nChallenge: {}\nTask: {} of {}\nFPS: {:.0}\nEntities: {}",
+            state.get(),
+            game_state.challenge.challenge_config.id,
+            game_state.current_task_index + 1,
+            game_state.task_count(),
+            *fps,
+            entities.len(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_console_commands() {
+        assert_eq!(
+            "start challenge konnektoren-1".parse(),
+            Ok(DebugCommand::StartChallenge("konnektoren-1".to_string()))
+        );
+        assert_eq!(" goto  map ".parse(), Ok(DebugCommand::Goto(AppState::Map)));
+        assert_eq!(
+            "complete challenge".parse(),
+            Ok(DebugCommand::CompleteChallenge)
+        );
+        assert!("fly".parse::<DebugCommand>().is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    app_state::AppState,
+    game::events::{ChallengeStarted, GameAction},
+    game_state::GameState,
+    screen::TransitionTo,
+};
+
+/// Query parameter naming the challenge a link opens.
+const CHALLENGE_PARAMETER: &str = "challenge";
+
+/// Screen a link leads to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Route {
+    Map,
+    Challenge(String),
+}
+
+impl Route {
+    /// The route of a URL query such as `?challenge=konnektoren-1`.
+    pub fn from_query(query: &str) -> Self {
+        query_pairs(query)
+            .into_iter()
+            .find(|(name, _)| name == CHALLENGE_PARAMETER)
+            .map_or(Self::Map, |(_, id)| Self::Challenge(id))
+    }
+
+    /// `query` leading to this route, keeping its other parameters such as the language.
+    pub fn to_query(&self, query: &str) -> String {
+        let mut pairs: Vec<(String, String)> = query_pairs(query)
+            .into_iter()
+            .filter(|(name, _)| name != CHALLENGE_PARAMETER)
+            .collect();
+        if let Self::Challenge(id) = self {
+            pairs.push((CHALLENGE_PARAMETER.to_string(), id.clone()));
+        }
+        if pairs.is_empty() {
+            return String::new();
+        }
+        let pairs: Vec<String> = pairs
+            .iter()
+            .map(|(name, value)| format!("{}={}", percent_encode(name), percent_encode(value)))
+            .collect();
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+/// Decoded name and value of each parameter of a URL query or hash, with or without its
+/// leading `?` or `#`.
+pub fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .trim_start_matches(['?', '#'])
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = text
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match byte {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Asks to show the screen of a route, e.g. after the browser went back to it.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct FollowRoute(pub Route);
+
+/// Route of the screen being shown; `None` on the splash screen and the intro.
+#[derive(Resource, Default, Debug, PartialEq, Eq)]
+pub struct CurrentRoute(pub Option<Route>);
+
+pub fn deep_link_plugin(app: &mut App) {
+    app.add_event::<FollowRoute>()
+        .init_resource::<CurrentRoute>()
+        .add_systems(
+            Update,
+            (
+                follow_route.run_if(on_event::<FollowRoute>()),
+                // Also on a new challenge, which may start without leaving the game screen.
+                update_route
+                    .run_if(state_changed::<AppState>.or_else(on_event::<ChallengeStarted>())),
+            ),
+        );
+
+    #[cfg(target_arch = "wasm32")]
+    app.add_systems(Startup, web::listen_for_navigation)
+        .add_systems(PreUpdate, web::follow_navigation)
+        .add_systems(
+            PostUpdate,
+            web::push_history.run_if(resource_changed::<CurrentRoute>),
+        );
+}
+
+fn update_route(
+    state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    mut route: ResMut<CurrentRoute>,
+) {
+    let current = match state.get() {
+        AppState::Map => Some(Route::Map),
+        AppState::Game => Some(Route::Challenge(
+            game_state.challenge.challenge_config.id.clone(),
+        )),
+        AppState::Splash | AppState::Slides | AppState::Error => None,
+    };
+    if route.0 != current {
+        route.0 = current;
+    }
+}
+
+fn follow_route(
+    mut routes: EventReader<FollowRoute>,
+    game_state: Res<GameState>,
+    mut transitions: EventWriter<TransitionTo>,
+    mut actions: EventWriter<GameAction>,
+) {
+    let Some(FollowRoute(route)) = routes.read().last() else {
+        return;
+    };
+    match route {
+        Route::Challenge(id)
+            if game_state
+                .game
+                .game_path
+                .challenges
+                .iter()
+                .any(|challenge| challenge.id == *id) =>
+        {
+            actions.send(GameAction::StartChallenge(id.clone()));
+        }
+        Route::Challenge(id) => {
+            log::error!("No challenge with id {}, showing the map instead", id);
+            transitions.send(TransitionTo(AppState::Map));
+        }
+        Route::Map => {
+            transitions.send(TransitionTo(AppState::Map));
+        }
+    }
+}
+
+/// Keeps the page URL in step with the screen, so links can be shared and the browser's
+/// back and forward buttons move between the map and challenges.
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::sync::{Arc, Mutex};
+
+    use bevy::{prelude::*, window::RequestRedraw, winit::EventLoopProxy};
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    use super::{CurrentRoute, FollowRoute, Route};
+
+    /// Routes the browser navigated to since the last frame.
+    #[derive(Resource, Clone, Default)]
+    pub(super) struct VisitedRoutes(Arc<Mutex<Vec<Route>>>);
+
+    /// Listens for `popstate`, which the browser fires for its back and forward buttons but
+    /// not for the entries the game pushes itself.
+    pub(super) fn listen_for_navigation(
+        mut commands: Commands,
+        proxy: Option<NonSend<EventLoopProxy>>,
+    ) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let visited = VisitedRoutes::default();
+        let queue = visited.clone();
+        // Navigating does not count as input, so it has to wake the event loop itself.
+        let proxy = proxy.map(|proxy| proxy.clone());
+        let location = window.location();
+        let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            let query = location.search().unwrap_or_default();
+            queue.0.lock().unwrap().push(Route::from_query(&query));
+            if let Some(proxy) = &proxy {
+                let _ = proxy.send_event(RequestRedraw);
+            }
+        });
+        if window
+            .add_event_listener_with_callback("popstate", listener.as_ref().unchecked_ref())
+            .is_ok()
+        {
+            // The listener lives as long as the page.
+            listener.forget();
+            commands.insert_resource(visited);
+        }
+    }
+
+    pub(super) fn follow_navigation(
+        visited: Option<Res<VisitedRoutes>>,
+        mut follow: EventWriter<FollowRoute>,
+    ) {
+        let Some(visited) = visited else {
+            return;
+        };
+        let Ok(mut queue) = visited.0.lock() else {
+            return;
+        };
+        follow.send_batch(queue.drain(..).map(FollowRoute));
+    }
+
+    pub(super) fn push_history(route: Res<CurrentRoute>) {
+        let Some(route) = route.0.as_ref() else {
+            return;
+        };
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let location = window.location();
+        let Ok(query) = location.search() else {
+            return;
+        };
+        let target = route.to_query(&query);
+        if target == query {
+            return;
+        }
+        let url = if target.is_empty() {
+            location.pathname().unwrap_or_default()
+        } else {
+            target
+        };
+        if let Ok(history) = window.history() {
+            let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&url));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_and_writes_challenge_links() {
+        assert_eq!(
+            Route::from_query("?challenge=konnektoren-1&lang=de"),
+            Route::Challenge("konnektoren-1".to_string())
+        );
+        assert_eq!(Route::from_query("#lang=de"), Route::Map);
+        assert_eq!(
+            query_pairs("?name=sowohl+%E2%80%A6+als%20auch"),
+            [("name".to_string(), "sowohl … als auch".to_string())]
+        );
+
+        let challenge = Route::Challenge("a b".to_string());
+        assert_eq!(challenge.to_query("?lang=de"), "?lang=de&challenge=a%20b");
+        assert_eq!(Route::Map.to_query("?lang=de&challenge=x"), "?lang=de");
+        assert_eq!(Route::Map.to_query("?challenge=x"), "");
+    }
+}
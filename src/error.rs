@@ -0,0 +1,221 @@
+use std::fmt;
+
+use bevy::{log, prelude::*};
+
+use crate::{
+    accessibility::LiveRegion,
+    app_state::AppState,
+    button::{pressed, set_toggle_label, spawn_button, ButtonLabel, ButtonPresses, ButtonSpec},
+    i18n::LocalizedText,
+    prelude::despawn_screen,
+    screen::TransitionTo,
+    theme::{FontSize, ThemeColor, ThemedBackground, ThemedText, UiTheme},
+};
+
+/// Problem that keeps the player from going on, shown on the error screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameError {
+    /// Id of the message telling the player what went wrong, e.g. `error-challenge`.
+    pub message: &'static str,
+    /// Technical details, shown on request and in bug reports.
+    pub details: String,
+}
+
+/// Where every screen reports errors instead of panicking; the first one is shown on the
+/// error screen until the player leaves it, as later ones tend to follow from it.
+#[derive(Resource, Default, Debug)]
+pub struct ErrorReport {
+    current: Option<GameError>,
+}
+
+impl ErrorReport {
+    /// Logs the error and keeps it unless an earlier one is still shown. Only a kept error
+    /// marks `errors` as changed, so an ignored one does not show the error screen again.
+    pub fn report(
+        errors: &mut impl DetectChangesMut<Inner = Self>,
+        message: &'static str,
+        error: impl fmt::Display,
+    ) {
+        log::error!("{}: {}", message, error);
+        if errors.bypass_change_detection().current.is_some() {
+            return;
+        }
+        errors.set_changed();
+        errors.bypass_change_detection().current = Some(GameError {
+            message,
+            details: error.to_string(),
+        });
+    }
+
+    pub fn current(&self) -> Option<&GameError> {
+        self.current.as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Screen the error screen offers to return to, or `None` to offer none, e.g. in the
+/// challenge player, which has no map.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorRecovery(pub Option<AppState>);
+
+impl Default for ErrorRecovery {
+    fn default() -> Self {
+        Self(Some(AppState::Map))
+    }
+}
+
+pub fn error_plugin(app: &mut App) {
+    app.init_resource::<ErrorReport>()
+        .init_resource::<ErrorRecovery>()
+        .add_systems(
+            Update,
+            show_error_screen
+                .run_if(resource_changed::<ErrorReport>.and_then(not(in_state(AppState::Error)))),
+        )
+        .add_systems(OnEnter(AppState::Error), error_setup)
+        .add_systems(
+            Update,
+            (details_toggle_system, recovery_system).run_if(in_state(AppState::Error)),
+        )
+        .add_systems(OnExit(AppState::Error), despawn_screen::<OnErrorScreen>);
+}
+
+#[derive(Component)]
+struct OnErrorScreen;
+
+/// The technical details, collapsed until asked for.
+#[derive(Component)]
+struct ErrorDetails;
+
+#[derive(Component)]
+struct DetailsToggle;
+
+#[derive(Component)]
+struct Recover;
+
+fn show_error_screen(report: Res<ErrorReport>, mut transitions: EventWriter<TransitionTo>) {
+    if report.current().is_some() {
+        transitions.send(TransitionTo(AppState::Error));
+    }
+}
+
+/// Message id of the button returning to `state`.
+fn recovery_label(state: AppState) -> &'static str {
+    match state {
+        AppState::Slides => "error-return-to-intro",
+        _ => "error-return-to-map",
+    }
+}
+
+fn error_setup(
+    mut commands: Commands,
+    report: Res<ErrorReport>,
+    recovery: Res<ErrorRecovery>,
+    theme: Res<UiTheme>,
+) {
+    let (message, details) = report
+        .current()
+        .map_or(("error-unknown", String::new()), |error| {
+            (error.message, error.details.clone())
+        });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(theme.spacing.large),
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: theme.palette.background.into(),
+                ..default()
+            },
+            ThemedBackground(ThemeColor::Background),
+            OnErrorScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("", theme.text_style(FontSize::Large)),
+                ThemedText(ThemeColor::Text, FontSize::Large),
+                LocalizedText::new("error-title"),
+            ));
+            parent.spawn((
+                TextBundle::from_section("", theme.text_style(FontSize::Medium))
+                    .with_text_justify(JustifyText::Center),
+                ThemedText(ThemeColor::Text, FontSize::Medium),
+                LocalizedText::new(message),
+                LiveRegion,
+            ));
+            spawn_button(
+                parent,
+                &theme,
+                ButtonSpec::localized(LocalizedText::new("error-show-details"))
+                    .with_font_size(FontSize::Medium),
+                DetailsToggle,
+            );
+            parent.spawn((
+                TextBundle::from_section(details, theme.text_style(FontSize::Small)).with_style(
+                    Style {
+                        display: Display::None,
+                        max_width: Val::Percent(90.0),
+                        ..default()
+                    },
+                ),
+                ThemedText(ThemeColor::Text, FontSize::Small),
+                ErrorDetails,
+            ));
+            if let Some(state) = recovery.0 {
+                spawn_button(
+                    parent,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new(recovery_label(state))),
+                    Recover,
+                );
+            }
+        });
+}
+
+fn details_toggle_system(
+    presses: ButtonPresses<DetailsToggle>,
+    mut details: Query<&mut Style, With<ErrorDetails>>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<DetailsToggle>>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    let mut shown = false;
+    for mut style in details.iter_mut() {
+        shown = style.display == Display::None;
+        style.display = if shown { Display::Flex } else { Display::None };
+    }
+    let label = if shown {
+        "error-hide-details"
+    } else {
+        "error-show-details"
+    };
+    set_toggle_label(&toggles, &mut labels, LocalizedText::new(label));
+}
+
+fn recovery_system(
+    presses: ButtonPresses<Recover>,
+    recovery: Res<ErrorRecovery>,
+    mut report: ResMut<ErrorReport>,
+    mut transitions: EventWriter<TransitionTo>,
+) {
+    let Some(state) = recovery.0 else {
+        return;
+    };
+    if pressed(&presses).next().is_some() {
+        report.clear();
+        transitions.send(TransitionTo(state));
+    }
+}
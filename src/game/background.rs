@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::{app_state::AppState, prelude::despawn_screen};
+use crate::{
+    app_state::AppState,
+    prelude::despawn_screen,
+    theme::{ThemeColor, ThemedBackground, UiTheme},
+};
 
 pub struct BackgroundPlugin;
 
@@ -11,7 +15,7 @@ impl Plugin for BackgroundPlugin {
     }
 }
 
-fn setup(mut commands: Commands) {
+fn setup(mut commands: Commands, theme: Res<UiTheme>) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -19,11 +23,11 @@ fn setup(mut commands: Commands) {
                 height: Val::Percent(100.0),
                 ..default()
             },
-            background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+            background_color: theme.palette.background.into(),
             z_index: ZIndex::Local(-1),
             ..default()
         })
-        .insert(GameBackground);
+        .insert((GameBackground, ThemedBackground(ThemeColor::Background)));
 }
 
 #[derive(Component)]
@@ -0,0 +1,133 @@
+use bevy::{ecs::system::SystemParam, log, prelude::*};
+use konnektoren_core::commands::{game_commands::SolveOptionCommand, GameCommand};
+
+use super::events::{
+    AnswerEvaluated, ChallengeCompleted, ChallengeStarted, GameAction, OptionChosen, TaskAdvanced,
+};
+use crate::{app_state::AppState, error::ErrorReport, game_state::GameState, screen::TransitionTo};
+
+pub struct CommandsPlugin;
+
+impl Plugin for CommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GameAction>()
+            .add_event::<ChallengeStarted>()
+            .add_event::<OptionChosen>()
+            .add_event::<AnswerEvaluated>()
+            .add_event::<TaskAdvanced>()
+            .add_event::<ChallengeCompleted>()
+            .init_resource::<ErrorReport>()
+            .add_systems(PostUpdate, execute_actions.in_set(ExecuteActions));
+    }
+}
+
+/// Executes the [`GameAction`]s sent during the frame.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExecuteActions;
+
+#[derive(SystemParam)]
+struct GameEvents<'w> {
+    challenge_started: EventWriter<'w, ChallengeStarted>,
+    option_chosen: EventWriter<'w, OptionChosen>,
+    answer_evaluated: EventWriter<'w, AnswerEvaluated>,
+    task_advanced: EventWriter<'w, TaskAdvanced>,
+    challenge_completed: EventWriter<'w, ChallengeCompleted>,
+}
+
+fn execute_actions(
+    mut actions: EventReader<GameAction>,
+    mut game_state: ResMut<GameState>,
+    mut transitions: EventWriter<TransitionTo>,
+    mut events: GameEvents,
+    mut errors: ResMut<ErrorReport>,
+) {
+    for action in actions.read() {
+        match action {
+            GameAction::StartChallenge(id) => {
+                if start_challenge(
+                    id,
+                    &mut game_state,
+                    &mut events.challenge_started,
+                    &mut errors,
+                ) {
+                    transitions.send(TransitionTo(AppState::Game));
+                }
+            }
+            GameAction::SolveOption(option_index) => {
+                solve_option(*option_index, &mut game_state, &mut events);
+            }
+        }
+    }
+}
+
+/// Sets up the challenge `id` from its first task, reporting an error if there is no such
+/// challenge. Returns whether it was started.
+pub(crate) fn start_challenge(
+    id: &str,
+    game_state: &mut GameState,
+    started: &mut EventWriter<ChallengeStarted>,
+    errors: &mut ResMut<ErrorReport>,
+) -> bool {
+    match game_state.game.create_challenge(id) {
+        Ok(challenge) => {
+            game_state.challenge = challenge;
+            game_state.current_task_index = 0;
+            started.send(ChallengeStarted {
+                challenge_id: id.to_string(),
+            });
+            log::info!("Started challenge {}", id);
+            true
+        }
+        Err(e) => {
+            ErrorReport::report(
+                errors,
+                "error-challenge",
+                format!("Challenge {}: {}", id, e),
+            );
+            false
+        }
+    }
+}
+
+fn solve_option(option_index: usize, game_state: &mut GameState, events: &mut GameEvents) {
+    if game_state.is_completed() {
+        log::warn!("Option {} chosen after challenge completion", option_index);
+        return;
+    }
+
+    let task_index = game_state.current_task_index;
+    let answered = game_state.answers().len();
+
+    let command = SolveOptionCommand { option_index };
+    let result = command.execute(game_state);
+
+    let answers = game_state.answers();
+    if answers.len() == answered {
+        if let Err(e) = result {
+            log::error!("Error solving option: {}", e);
+        }
+        return;
+    }
+
+    events.option_chosen.send(OptionChosen {
+        task_index,
+        option_index,
+    });
+    events.answer_evaluated.send(AnswerEvaluated {
+        task_index,
+        correct: answers[answered],
+    });
+
+    if game_state.current_task_index != task_index {
+        events.task_advanced.send(TaskAdvanced {
+            task_index: game_state.current_task_index,
+        });
+    }
+
+    if game_state.is_completed() {
+        events.challenge_completed.send(ChallengeCompleted {
+            challenge_id: game_state.challenge.challenge_config.id.clone(),
+            score: game_state.score(),
+        });
+    }
+}
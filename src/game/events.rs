@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A player action, executed as a game command by the commands plugin.
+///
+/// Screens send these instead of mutating [`GameState`](crate::game_state::GameState)
+/// directly; everything that follows from an action is announced through the events below.
+#[derive(Event, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GameAction {
+    StartChallenge(String),
+    SolveOption(usize),
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ChallengeStarted {
+    pub challenge_id: String,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct OptionChosen {
+    pub task_index: usize,
+    pub option_index: usize,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct AnswerEvaluated {
+    pub task_index: usize,
+    pub correct: bool,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TaskAdvanced {
+    pub task_index: usize,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ChallengeCompleted {
+    pub challenge_id: String,
+    /// Percentage of correctly answered tasks.
+    pub score: f32,
+}
@@ -1,16 +1,34 @@
-use bevy::app::{App, Plugin};
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
 
 mod background;
+mod commands;
+pub mod events;
 mod options;
 mod question;
 mod results;
 mod tasks;
 
+pub use commands::ExecuteActions;
+pub(crate) use commands::{start_challenge, CommandsPlugin};
+pub use options::{OptionLayout, OptionNode};
+
+/// Whether another challenge was started while the game screen is shown. The state stays the
+/// same then, so `OnEnter` does not run again and the screen is rebuilt on this instead.
+pub(crate) fn challenge_restarted(
+    mut started: EventReader<events::ChallengeStarted>,
+    state: Res<State<AppState>>,
+) -> bool {
+    started.read().count() > 0 && *state.get() == AppState::Game && !state.is_changed()
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            commands::CommandsPlugin,
             tasks::TasksPlugin,
             question::QuestionPlugin,
             options::OptionsPlugin,
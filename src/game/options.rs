@@ -1,17 +1,36 @@
-use bevy::{log, prelude::*};
-use konnektoren_core::{
-    challenges::ChallengeType,
-    commands::{game_commands::SolveOptionCommand, GameCommand},
+use bevy::{
+    log,
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
 };
+use konnektoren_core::challenges::ChallengeType;
 
-use crate::{app_state::AppState, game_state::GameState, prelude::despawn_screen};
+use super::{challenge_restarted, events::GameAction};
+use crate::{
+    app_state::AppState,
+    button::{pressed, spawn_button, ButtonPresses, ButtonSpec},
+    game_state::GameState,
+    i18n::MirrorRightToLeft,
+    prelude::despawn_screen,
+    theme::{FontSize, ThemeColor, ThemedBackground, UiTheme},
+};
 
 pub struct OptionsPlugin;
 
 impl Plugin for OptionsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Game), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Game)))
+            .add_systems(
+                Update,
+                (update, adapt_layout.run_if(on_event::<WindowResized>()))
+                    .run_if(in_state(AppState::Game)),
+            )
+            .add_systems(
+                Update,
+                (despawn_screen::<OptionsEntity>, setup)
+                    .chain()
+                    .run_if(challenge_restarted),
+            )
             .add_systems(OnExit(AppState::Game), despawn_screen::<OptionsEntity>);
     }
 }
@@ -20,45 +39,108 @@ impl Plugin for OptionsPlugin {
 struct OptionsEntity;
 
 #[derive(Component)]
-struct OptionNode {
-    index: usize,
-    name: String,
+pub struct OptionNode {
+    pub index: usize,
+    pub name: String,
 }
 
-fn create_button(commands: &mut ChildBuilder, index: usize, text: &str, font: Handle<Font>) {
-    commands
-        .spawn((
-            ButtonBundle {
-                style: Style {
-                    width: Val::Px(100.0),
-                    height: Val::Px(100.0),
-                    margin: UiRect::all(Val::Px(5.0)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    padding: UiRect::all(Val::Px(5.0)),
-                    ..default()
-                },
-                background_color: Color::rgb(0.25, 0.25, 0.25).into(),
-                ..default()
+/// Windows narrower than this show the options as a list, whatever their orientation.
+const LIST_MAX_WIDTH: f32 = 480.0;
+
+/// Arrangement of the options, chosen by the shape of the window.
+#[derive(Component, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum OptionLayout {
+    /// Rows of buttons sized to their text, wrapping onto further rows.
+    #[default]
+    Grid,
+    /// One full-width button per line, for portrait and narrow windows.
+    List,
+}
+
+impl OptionLayout {
+    pub fn for_window(width: f32, height: f32) -> Self {
+        if height > width || width < LIST_MAX_WIDTH {
+            Self::List
+        } else {
+            Self::Grid
+        }
+    }
+
+    fn of(windows: &Query<&Window, With<PrimaryWindow>>) -> Self {
+        windows
+            .get_single()
+            .map(|window| Self::for_window(window.width(), window.height()))
+            .unwrap_or_default()
+    }
+
+    fn container_style(self, theme: &UiTheme) -> Style {
+        let style = Style {
+            width: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(theme.spacing.large),
+            align_self: AlignSelf::End,
+            ..default()
+        };
+        match self {
+            Self::Grid => Style {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::SpaceAround,
+                align_items: AlignItems::Center,
+                ..style
             },
-            OptionNode {
-                index,
-                name: text.to_string(),
+            Self::List => Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                ..style
             },
-        ))
-        .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                text,
-                TextStyle {
-                    font,
-                    font_size: 18.0,
-                    color: Color::WHITE,
-                },
-            ));
-        });
+        }
+    }
+
+    fn button_style(self, theme: &UiTheme) -> Style {
+        match self {
+            // Grows with the text, wrapping it once half the row is taken.
+            Self::Grid => Style {
+                min_width: Val::Px(100.0),
+                min_height: Val::Px(100.0),
+                max_width: Val::Percent(45.0),
+                ..theme.button_style()
+            },
+            Self::List => Style {
+                min_height: Val::Px(48.0),
+                ..theme.button_style()
+            },
+        }
+    }
 }
 
-fn setup(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>) {
+fn create_button(
+    commands: &mut ChildBuilder,
+    index: usize,
+    text: &str,
+    theme: &UiTheme,
+    layout: OptionLayout,
+) {
+    spawn_button(
+        commands,
+        theme,
+        ButtonSpec::label(text)
+            .with_font_size(FontSize::Medium)
+            .with_style(layout.button_style(theme)),
+        OptionNode {
+            index,
+            name: text.to_string(),
+        },
+    );
+}
+
+fn setup(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    theme: Res<UiTheme>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let layout = OptionLayout::of(&windows);
     commands
         .spawn((
             NodeBundle {
@@ -75,50 +157,70 @@ fn setup(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<A
             OptionsEntity,
         ))
         .with_children(|parent| {
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        width: Val::Percent(100.0),
-                        height: Val::Px(40.0),
-                        position_type: PositionType::Absolute,
-                        bottom: Val::Px(20.0),
-                        flex_direction: FlexDirection::Row,
-                        justify_content: JustifyContent::SpaceAround,
-                        align_items: AlignItems::Center,
-                        align_self: AlignSelf::End,
-                        ..default()
-                    },
-                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+            let mut container = parent.spawn((
+                NodeBundle {
+                    style: layout.container_style(&theme),
+                    background_color: theme.palette.surface.into(),
                     ..default()
-                })
-                .with_children(|buttons| match game_state.challenge.challenge_type {
-                    ChallengeType::MultipleChoice(ref dataset) => {
-                        for (index, option) in dataset.options.iter().enumerate() {
-                            let text = format!("{}. {}", index + 1, option.name);
-                            create_button(
-                                buttons,
-                                index,
-                                &text,
-                                asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            );
-                        }
+                },
+                ThemedBackground(ThemeColor::Surface),
+                layout,
+            ));
+            if layout == OptionLayout::Grid {
+                container.insert(MirrorRightToLeft);
+            }
+            container.with_children(|buttons| match game_state.challenge.challenge_type {
+                ChallengeType::MultipleChoice(ref dataset) => {
+                    for (index, option) in dataset.options.iter().enumerate() {
+                        let text = format!("{}. {}", index + 1, option.name);
+                        create_button(buttons, index, &text, &theme, layout);
                     }
-                });
+                }
+            });
         });
 }
 
-fn update(
-    mut interaction_query: Query<(&Interaction, &OptionNode), (Changed<Interaction>, With<Button>)>,
-    mut game_state: ResMut<GameState>,
+/// Rearranges the options when the window turns between landscape and portrait. Only rows
+/// are mirrored for right-to-left languages, as a list reads the same either way.
+fn adapt_layout(
+    mut commands: Commands,
+    theme: Res<UiTheme>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut containers: Query<(Entity, &mut OptionLayout, &mut Style)>,
+    mut buttons: Query<&mut Style, (With<OptionNode>, Without<OptionLayout>)>,
 ) {
-    for (interaction, option) in interaction_query.iter_mut() {
-        if *interaction == Interaction::Pressed {
-            let command = SolveOptionCommand {
-                option_index: option.index,
-            };
-
-            command.execute(&mut game_state).unwrap();
-            log::info!("Option selected: {}", option.name);
+    let layout = OptionLayout::of(&windows);
+    for (container, mut current, mut style) in containers.iter_mut() {
+        if *current == layout {
+            continue;
+        }
+        *current = layout;
+        *style = layout.container_style(&theme);
+        match layout {
+            OptionLayout::Grid => commands.entity(container).insert(MirrorRightToLeft),
+            OptionLayout::List => commands.entity(container).remove::<MirrorRightToLeft>(),
+        };
+        for mut button in buttons.iter_mut() {
+            *button = layout.button_style(&theme);
         }
     }
 }
+
+fn update(presses: ButtonPresses<OptionNode>, mut actions: EventWriter<GameAction>) {
+    for option in pressed(&presses) {
+        actions.send(GameAction::SolveOption(option.index));
+        log::info!("Option selected: {}", option.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portrait_and_narrow_windows_list_the_options() {
+        assert_eq!(OptionLayout::for_window(1280.0, 720.0), OptionLayout::Grid);
+        assert_eq!(OptionLayout::for_window(720.0, 1280.0), OptionLayout::List);
+        assert_eq!(OptionLayout::for_window(400.0, 300.0), OptionLayout::List);
+    }
+}
@@ -1,14 +1,32 @@
 use bevy::prelude::*;
 use konnektoren_core::challenges::ChallengeType;
 
-use crate::{app_state::AppState, game_state::GameState, prelude::despawn_screen};
+use super::{challenge_restarted, events::TaskAdvanced};
+use crate::{
+    accessibility::LiveRegion,
+    app_state::AppState,
+    error::ErrorReport,
+    game_state::GameState,
+    i18n::LocalizedText,
+    prelude::despawn_screen,
+    theme::{FontSize, ThemeColor, ThemedText, UiTheme},
+};
 
 pub struct QuestionPlugin;
 
 impl Plugin for QuestionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Game), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Game)))
+            .add_systems(
+                Update,
+                update.run_if(in_state(AppState::Game).and_then(on_event::<TaskAdvanced>())),
+            )
+            .add_systems(
+                Update,
+                (despawn_screen::<QuestionEntity>, setup)
+                    .chain()
+                    .run_if(challenge_restarted),
+            )
             .add_systems(OnExit(AppState::Game), despawn_screen::<QuestionEntity>);
     }
 }
@@ -16,54 +34,77 @@ impl Plugin for QuestionPlugin {
 #[derive(Component)]
 struct QuestionEntity;
 
-fn setup(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
-    let text_style = TextStyle {
-        font: font.clone(),
-        font_size: 20.0,
-        color: Color::WHITE,
+fn setup(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    theme: Res<UiTheme>,
+    mut errors: ResMut<ErrorReport>,
+) {
+    let text = match question_text(&game_state) {
+        Ok(text) => text,
+        Err(e) => {
+            ErrorReport::report(&mut errors, "error-content", e);
+            return;
+        }
     };
+    let text_style = theme.text_style(FontSize::Large);
     let text_justification = JustifyText::Center;
 
     let style = Style {
         align_items: AlignItems::Center,
-        width: Val::Percent(100.0),
+        // Centred with room to wrap, so large text and portrait windows keep it on screen.
+        width: Val::Percent(90.0),
+        max_width: Val::Px(640.0),
+        margin: UiRect::horizontal(Val::Auto),
         position_type: PositionType::Relative,
         top: Val::Percent(20.0),
-        left: Val::Percent(20.0),
         ..default()
     };
 
-    let current_question = game_state.current_task_index;
-
-    let text = match game_state.challenge.challenge_type {
-        ChallengeType::MultipleChoice(ref dataset) => {
-            let question = dataset.questions.get(current_question).unwrap();
-
-            format!("Question: {}\n\n{}", question.question, question.help)
-        }
-    };
-
     commands.spawn((
-        TextBundle::from_section(text, text_style)
+        TextBundle::from_section("", text_style)
             .with_text_justify(text_justification)
             .with_style(style),
+        ThemedText(ThemeColor::Text, FontSize::Large),
+        text,
+        LiveRegion,
         QuestionEntity,
     ));
 }
 
-fn update(mut query: Query<(&QuestionEntity, &mut Text)>, game_state: Res<GameState>) {
-    for (_, mut text) in &mut query.iter_mut() {
-        let current_question = game_state.current_task_index;
+fn question_text(game_state: &GameState) -> anyhow::Result<LocalizedText> {
+    let current_question = game_state.current_task_index;
 
-        let question = match game_state.challenge.challenge_type {
-            ChallengeType::MultipleChoice(ref dataset) => {
-                let question = dataset.questions.get(current_question).unwrap();
+    match game_state.challenge.challenge_type {
+        ChallengeType::MultipleChoice(ref dataset) => {
+            let question = dataset.questions.get(current_question).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Challenge {} has no question {}",
+                    game_state.challenge.challenge_config.id,
+                    current_question + 1
+                )
+            })?;
 
-                format!("Question: {}\n\n{}", question.question, question.help)
-            }
-        };
+            Ok(LocalizedText::new("game-question")
+                .with_arg("question", question.question.as_str())
+                .with_arg("help", question.help.as_str()))
+        }
+    }
+}
 
-        text.sections[0].value = question;
+fn update(
+    mut query: Query<&mut LocalizedText, With<QuestionEntity>>,
+    game_state: Res<GameState>,
+    mut errors: ResMut<ErrorReport>,
+) {
+    let question = match question_text(&game_state) {
+        Ok(question) => question,
+        Err(e) => {
+            ErrorReport::report(&mut errors, "error-content", e);
+            return;
+        }
+    };
+    for mut text in query.iter_mut() {
+        *text = question.clone();
     }
 }
@@ -1,18 +1,35 @@
 use bevy::prelude::*;
-use konnektoren_core::challenges::{ChallengeResult, ChallengeType};
+use konnektoren_core::challenges::ChallengeType;
 
-use crate::{app_state::AppState, game_state::GameState, prelude::despawn_screen};
+use super::{challenge_restarted, events::AnswerEvaluated};
+use crate::{
+    accessibility::Announcement,
+    app_state::AppState,
+    game_state::GameState,
+    i18n::LocalizedText,
+    prelude::despawn_screen,
+    theme::{FontSize, Palette, SymbolText, ThemeColor, ThemedText, UiTheme},
+};
 
 pub struct ResultsPlugin;
 
 impl Plugin for ResultsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DisplayedResults>()
-            .add_systems(OnEnter(AppState::Game), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Game)))
+        app.add_systems(OnEnter(AppState::Game), setup)
             .add_systems(
                 Update,
-                update_background_color.run_if(in_state(AppState::Game)),
+                update.run_if(in_state(AppState::Game).and_then(resource_changed::<GameState>)),
+            )
+            .add_systems(
+                Update,
+                update_background_color
+                    .run_if(in_state(AppState::Game).and_then(on_event::<AnswerEvaluated>())),
+            )
+            .add_systems(
+                Update,
+                (despawn_screen::<ResultsEntity>, setup)
+                    .chain()
+                    .run_if(challenge_restarted),
             )
             .add_systems(OnExit(AppState::Game), despawn_screen::<ResultsEntity>);
     }
@@ -21,160 +38,220 @@ impl Plugin for ResultsPlugin {
 #[derive(Component)]
 struct ResultsEntity;
 
+/// Summary of the challenge, coloured by the result of the last answer.
 #[derive(Component)]
 struct ResultsBackground;
 
-#[derive(Default, Resource)]
-struct DisplayedResults {
-    count: usize,
+#[derive(Component)]
+struct ResultsSummary;
+
+/// A line of the results list, keyed by the index of the question it reports on.
+#[derive(Component)]
+struct ResultRow(usize);
+
+#[derive(Component)]
+struct ResultMessage;
+
+/// Check or cross next to a result, repeating its colour as a shape.
+#[derive(Component)]
+struct ResultMark;
+
+const CHECK_MARK: &str = "✓";
+const CROSS_MARK: &str = "✗";
+
+fn result_mark(correct: bool) -> &'static str {
+    if correct {
+        CHECK_MARK
+    } else {
+        CROSS_MARK
+    }
 }
 
-fn calculate_results(game_state: &GameState) -> Vec<String> {
-    let results = match (
-        &game_state.challenge.challenge_type,
-        &game_state.challenge.challenge_result,
-    ) {
-        (ChallengeType::MultipleChoice(dataset), ChallengeResult::MultipleChoice(options)) => {
-            dataset.questions.iter().zip(options.iter()).fold(
-                Vec::new(),
-                |mut acc, (question, option)| {
-                    let correct = if question.option == option.id {
-                        format!("Correct: {}", question.question)
-                    } else {
-                        format!("Incorrect: {}", question.question)
-                    };
-                    acc.push(correct);
-                    acc
-                },
-            )
-        }
+fn result_text(correct: bool, question: &str) -> LocalizedText {
+    let id = if correct {
+        "game-result-correct"
+    } else {
+        "game-result-incorrect"
     };
+    LocalizedText::new(id).with_arg("question", question)
+}
 
-    results
+/// Result line of each answered question, in task order.
+fn calculate_results(game_state: &GameState) -> Vec<LocalizedText> {
+    match &game_state.challenge.challenge_type {
+        ChallengeType::MultipleChoice(dataset) => dataset
+            .questions
+            .iter()
+            .zip(game_state.answers())
+            .map(|(question, correct)| result_text(correct, &question.question))
+            .collect(),
+    }
 }
 
-fn last_result_color(results: &[String]) -> Color {
-    results
+fn last_result_color(answers: &[bool], palette: &Palette) -> Color {
+    answers
         .last()
-        .map(|result| {
-            if result.starts_with("Correct") {
-                Color::rgb(0.0, 1.0, 0.0)
+        .map(|correct| {
+            if *correct {
+                palette.correct
             } else {
-                Color::rgb(1.0, 0.0, 0.0)
+                palette.incorrect
             }
         })
-        .unwrap_or(Color::rgb(0.1, 0.1, 0.1))
+        .unwrap_or(palette.background)
 }
 
-fn calculate_score(results: &[String], game_state: &GameState) -> f32 {
-    let num_tasks = match &game_state.challenge.challenge_type {
-        ChallengeType::MultipleChoice(dataset) => dataset.questions.len(),
-    };
-
-    let score = results
-        .iter()
-        .filter(|line| line.contains("Correct"))
-        .count() as u32;
-
-    score as f32 / num_tasks as f32 * 100.0
-}
+fn setup(mut commands: Commands, theme: Res<UiTheme>) {
+    let text_style = theme.text_style(FontSize::Large);
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut displayed_results: ResMut<DisplayedResults>,
-) {
-    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
-    let text_style = TextStyle {
-        font: font,
-        font_size: 20.0,
-        color: Color::WHITE,
-    };
-
-    commands.spawn((
-        TextBundle {
-            text: Text::from_section("Calculating score...", text_style.clone())
-                .with_justify(JustifyText::Center),
-            style: Style {
-                top: Val::Px(20.0),
-                left: Val::Px(10.0),
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    top: Val::Px(20.0),
+                    left: Val::Px(10.0),
+                    column_gap: Val::Px(theme.spacing.small),
+                    align_self: AlignSelf::FlexStart,
+                    ..default()
+                },
                 ..default()
             },
-            ..default()
-        },
-        ResultsBackground,
-        ResultsEntity,
-    ));
-
-    displayed_results.count = 0;
+            ResultsBackground,
+            ResultsEntity,
+        ))
+        .with_children(|summary| {
+            summary.spawn((
+                TextBundle::from_section("", theme.symbol_style(FontSize::Large)),
+                ThemedText(ThemeColor::Text, FontSize::Large),
+                SymbolText,
+                ResultMark,
+            ));
+            summary.spawn((
+                TextBundle {
+                    text: Text::from_section("", text_style).with_justify(JustifyText::Center),
+                    ..default()
+                },
+                ThemedText(ThemeColor::Text, FontSize::Large),
+                LocalizedText::new("game-calculating-score"),
+                ResultsSummary,
+            ));
+        });
 }
 
 fn update(
     mut commands: Commands,
-    mut results_query: Query<(Entity, &mut Text), With<ResultsEntity>>,
+    mut summary_query: Query<&mut LocalizedText, (With<ResultsSummary>, Without<ResultMessage>)>,
+    rows_query: Query<(Entity, &ResultRow, &Children)>,
+    mut marks: Query<&mut Text, With<ResultMark>>,
+    mut messages: Query<&mut LocalizedText, (With<ResultMessage>, Without<ResultsSummary>)>,
     game_state: Res<GameState>,
-    asset_server: Res<AssetServer>,
-    mut displayed_results: ResMut<DisplayedResults>,
+    theme: Res<UiTheme>,
 ) {
+    let answers = game_state.answers();
     let results = calculate_results(&game_state);
-    let score = calculate_score(&results, &game_state);
 
-    let mut entity_iter = results_query.iter_mut();
-    if let Some((_, mut overall_text)) = entity_iter.next() {
-        overall_text.sections[0].value = format!(
-            "You have completed the challenge with a score of {:.1}!",
-            score
-        );
+    if let Ok(mut overall_text) = summary_query.get_single_mut() {
+        // Formatted here, so the score keeps its decimal in every language.
+        let summary = LocalizedText::new("game-score")
+            .with_arg("score", format!("{:.1}", game_state.score()));
+        if *overall_text != summary {
+            *overall_text = summary;
+        }
+    }
+
+    let mut displayed = vec![false; results.len()];
+    for (entity, row, children) in rows_query.iter() {
+        match results.get(row.0) {
+            Some(result) => {
+                let mut texts = messages.iter_many_mut(children);
+                while let Some(mut text) = texts.fetch_next() {
+                    if *text != *result {
+                        *text = result.clone();
+                    }
+                }
+                let mut texts = marks.iter_many_mut(children);
+                while let Some(mut mark) = texts.fetch_next() {
+                    let value = result_mark(answers[row.0]);
+                    if mark.sections[0].value != value {
+                        mark.sections[0].value = value.to_string();
+                    }
+                }
+                displayed[row.0] = true;
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
     }
 
     let start_y = 60.0;
     let line_height = 30.0;
 
     for (index, result) in results.iter().enumerate() {
-        if index >= displayed_results.count {
-            commands
-                .spawn(TextBundle {
-                    text: Text::from_section(
-                        result.clone(),
-                        TextStyle {
-                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                            font_size: 20.0,
-                            color: Color::WHITE,
-                        },
-                    )
-                    .with_justify(JustifyText::Center),
+        if displayed[index] {
+            continue;
+        }
+        commands
+            .spawn((
+                NodeBundle {
                     style: Style {
                         align_self: AlignSelf::Center,
                         position_type: PositionType::Absolute,
                         top: Val::Px(start_y + index as f32 * line_height),
+                        column_gap: Val::Px(theme.spacing.small),
                         ..default()
                     },
                     ..default()
-                })
-                .insert(ResultsEntity);
-        } else {
-            if let Some((entity, mut text)) = entity_iter.next() {
-                text.sections[0].value = result.clone();
-                commands.entity(entity).insert(Style {
-                    top: Val::Px(start_y + index as f32 * line_height),
-                    ..default()
-                });
-            }
-        }
+                },
+                ResultRow(index),
+                ResultsEntity,
+            ))
+            .with_children(|row| {
+                row.spawn((
+                    TextBundle::from_section(
+                        result_mark(answers[index]),
+                        theme.symbol_style(FontSize::Large),
+                    ),
+                    ThemedText(ThemeColor::Text, FontSize::Large),
+                    SymbolText,
+                    ResultMark,
+                ));
+                row.spawn((
+                    TextBundle {
+                        text: Text::from_section("", theme.text_style(FontSize::Large))
+                            .with_justify(JustifyText::Center),
+                        ..default()
+                    },
+                    ThemedText(ThemeColor::Text, FontSize::Large),
+                    result.clone(),
+                    ResultMessage,
+                ));
+            });
     }
-
-    displayed_results.count = results.len();
 }
 
+/// Colours the summary after the last answer and marks it with a check or cross, so the
+/// result does not depend on telling the colours apart, and reads it out.
 fn update_background_color(
-    mut background_query: Query<&mut BackgroundColor, With<ResultsBackground>>,
+    mut background_query: Query<(&mut BackgroundColor, &Children), With<ResultsBackground>>,
+    mut marks: Query<&mut Text, With<ResultMark>>,
+    mut announcements: EventWriter<Announcement>,
     game_state: Res<GameState>,
+    theme: Res<UiTheme>,
 ) {
-    let results = calculate_results(&game_state);
-    let new_color = last_result_color(&results);
+    let answers = game_state.answers();
+    if let Some(result) = calculate_results(&game_state).pop() {
+        announcements.send(Announcement(result));
+    }
+    let new_color = last_result_color(&answers, &theme.palette);
 
-    if let Ok(mut background_color) = background_query.get_single_mut() {
+    if let Ok((mut background_color, children)) = background_query.get_single_mut() {
         *background_color = new_color.into();
+        let mut texts = marks.iter_many_mut(children);
+        while let Some(mut mark) = texts.fetch_next() {
+            mark.sections[0].value = answers
+                .last()
+                .map_or("", |correct| result_mark(*correct))
+                .to_string();
+        }
     }
 }
 
@@ -184,11 +261,9 @@ mod tests {
 
     #[test]
     fn test_last_result_color() {
-        let results = vec![
-            "Correct: What is 1 + 1?".to_string(),
-            "Incorrect: What is 2 + 2?".to_string(),
-        ];
-
-        assert_eq!(last_result_color(&results), Color::rgb(1.0, 0.0, 0.0));
+        assert_eq!(
+            last_result_color(&[true, false], &Palette::dark()),
+            Color::rgb(1.0, 0.0, 0.0)
+        );
     }
 }
@@ -1,13 +1,29 @@
 use bevy::prelude::*;
 
-use crate::{app_state::AppState, game_state::GameState, prelude::despawn_screen};
+use super::{challenge_restarted, events::TaskAdvanced};
+use crate::{
+    app_state::AppState,
+    game_state::GameState,
+    i18n::LocalizedText,
+    prelude::despawn_screen,
+    theme::{FontSize, ThemeColor, ThemedText, UiTheme},
+};
 
 pub struct TasksPlugin;
 
 impl Plugin for TasksPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Game), setup)
-            .add_systems(Update, update.run_if(in_state(AppState::Game)))
+            .add_systems(
+                Update,
+                update.run_if(in_state(AppState::Game).and_then(on_event::<TaskAdvanced>())),
+            )
+            .add_systems(
+                Update,
+                (despawn_screen::<TasksEntity>, setup)
+                    .chain()
+                    .run_if(challenge_restarted),
+            )
             .add_systems(OnExit(AppState::Game), despawn_screen::<TasksEntity>);
     }
 }
@@ -15,34 +31,33 @@ impl Plugin for TasksPlugin {
 #[derive(Component)]
 struct TasksEntity;
 
-fn setup(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
-    let text_style = TextStyle {
-        font: font.clone(),
-        font_size: 20.0,
-        color: Color::WHITE,
-    };
+fn setup(mut commands: Commands, game_state: Res<GameState>, theme: Res<UiTheme>) {
+    let text_style = theme.text_style(FontSize::Large);
     let text_justification = JustifyText::Center;
 
     let style = Style {
         position_type: PositionType::Absolute,
-        top: Val::Px(20.0),
-        right: Val::Px(20.0),
+        top: Val::Px(theme.spacing.large),
+        right: Val::Px(theme.spacing.large),
         ..default()
     };
 
-    let text = format!("Task: {}", game_state.current_task_index + 1);
-
     commands.spawn((
-        TextBundle::from_section(text, text_style)
+        TextBundle::from_section("", text_style)
             .with_text_justify(text_justification)
             .with_style(style),
+        ThemedText(ThemeColor::Text, FontSize::Large),
+        task_text(&game_state),
         TasksEntity,
     ));
 }
 
-fn update(mut query: Query<(&TasksEntity, &mut Text)>, game_state: Res<GameState>) {
-    for (_, mut text) in &mut query.iter_mut() {
-        text.sections[0].value = format!("Task: {}", game_state.current_task_index + 1);
+fn task_text(game_state: &GameState) -> LocalizedText {
+    LocalizedText::new("game-task").with_arg("number", game_state.current_task_index + 1)
+}
+
+fn update(mut query: Query<&mut LocalizedText, With<TasksEntity>>, game_state: Res<GameState>) {
+    for mut text in query.iter_mut() {
+        *text = task_text(&game_state);
     }
 }
@@ -2,6 +2,65 @@ use bevy::{
     ecs::system::Resource,
     prelude::{Deref, DerefMut},
 };
+use konnektoren_core::challenges::{ChallengeResult, ChallengeType};
 
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct GameState(konnektoren_core::game::GameState);
+
+impl GameState {
+    /// Number of tasks in the current challenge.
+    pub fn task_count(&self) -> usize {
+        match self.challenge.challenge_type {
+            ChallengeType::MultipleChoice(ref dataset) => dataset.questions.len(),
+        }
+    }
+
+    /// Whether each answered task was answered correctly, in task order.
+    pub fn answers(&self) -> Vec<bool> {
+        match (
+            &self.challenge.challenge_type,
+            &self.challenge.challenge_result,
+        ) {
+            (ChallengeType::MultipleChoice(dataset), ChallengeResult::MultipleChoice(options)) => {
+                dataset
+                    .questions
+                    .iter()
+                    .zip(options.iter())
+                    .map(|(question, option)| question.option == option.id)
+                    .collect()
+            }
+        }
+    }
+
+    /// Index of the option that answers the task `task_index` correctly.
+    pub fn correct_option(&self, task_index: usize) -> Option<usize> {
+        match self.challenge.challenge_type {
+            ChallengeType::MultipleChoice(ref dataset) => {
+                let question = dataset.questions.get(task_index)?;
+                dataset
+                    .options
+                    .iter()
+                    .position(|option| option.id == question.option)
+            }
+        }
+    }
+
+    /// Percentage of tasks answered correctly.
+    pub fn score(&self) -> f32 {
+        let num_tasks = self.task_count();
+        if num_tasks == 0 {
+            return 0.0;
+        }
+        let correct = self
+            .answers()
+            .into_iter()
+            .filter(|correct| *correct)
+            .count();
+        correct as f32 / num_tasks as f32 * 100.0
+    }
+
+    pub fn is_completed(&self) -> bool {
+        let num_tasks = self.task_count();
+        num_tasks > 0 && self.answers().len() >= num_tasks
+    }
+}
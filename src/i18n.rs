@@ -0,0 +1,317 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    log,
+    prelude::*,
+    ui::UiSystem,
+    utils::BoxedFuture,
+};
+use fluent::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::{preload::Preload, settings::Settings};
+
+/// Language every other one falls back to for messages it does not translate.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Languages the UI is translated into, each with its own name for it.
+pub const LANGUAGES: [(&str, &str); 3] =
+    [("en", "English"), ("de", "Deutsch"), ("uk", "Українська")];
+
+/// Languages written from right to left, by their base language.
+const RIGHT_TO_LEFT_LANGUAGES: [&str; 4] = ["ar", "fa", "he", "ur"];
+
+/// Path of the Fluent file holding the messages of `language`.
+fn locale_path(language: &str) -> String {
+    format!("locales/{}/main.ftl", language)
+}
+
+/// Source of a Fluent (`.ftl`) file.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct FluentSource(pub String);
+
+#[derive(Default)]
+pub struct FluentLoader;
+
+impl AssetLoader for FluentLoader {
+    type Asset = FluentSource;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<FluentSource, anyhow::Error>> {
+        Box::pin(async move {
+            let mut source = String::new();
+            reader.read_to_string(&mut source).await?;
+            Ok(FluentSource(source))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// Languages tried in order for `language`: itself, its base language, then the default.
+pub fn fallback_chain(language: &str) -> Vec<LanguageIdentifier> {
+    let mut chain = Vec::new();
+    if let Ok(id) = language.parse::<LanguageIdentifier>() {
+        let base = LanguageIdentifier::from_parts(id.language, None, None, &[]);
+        chain.push(id);
+        if !chain.contains(&base) {
+            chain.push(base);
+        }
+    }
+    if let Ok(default) = DEFAULT_LANGUAGE.parse() {
+        if !chain.contains(&default) {
+            chain.push(default);
+        }
+    }
+    chain
+}
+
+/// Whether `language`, or the base language of it, is written from right to left.
+pub fn is_right_to_left(language: &str) -> bool {
+    language
+        .parse::<LanguageIdentifier>()
+        .is_ok_and(|id| RIGHT_TO_LEFT_LANGUAGES.contains(&id.language.as_str()))
+}
+
+/// Value of a placeable in a localised message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LocaleArg {
+    Number(f64),
+    Text(String),
+}
+
+impl From<usize> for LocaleArg {
+    fn from(value: usize) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<f64> for LocaleArg {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<&str> for LocaleArg {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for LocaleArg {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// Message id and arguments a text is rendered from in the active language.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct LocalizedText {
+    pub id: String,
+    pub args: Vec<(String, LocaleArg)>,
+}
+
+impl LocalizedText {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<LocaleArg>) -> Self {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Messages of the active language and its fallbacks.
+#[derive(Resource, Default)]
+pub struct Localization {
+    language: String,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localization {
+    /// Builds the bundles for `language` from the Fluent sources of each language in its
+    /// fallback chain; languages without a source are skipped.
+    pub fn new<'a>(
+        language: &str,
+        sources: impl Fn(&LanguageIdentifier) -> Option<&'a str>,
+    ) -> Self {
+        let bundles = fallback_chain(language)
+            .into_iter()
+            .filter_map(|id| {
+                let source = sources(&id)?;
+                let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(
+                    |(resource, errors)| {
+                        log::error!("Errors parsing messages for {}: {:?}", id, errors);
+                        resource
+                    },
+                );
+                let mut bundle = FluentBundle::new_concurrent(vec![id.clone()]);
+                // Isolation marks show up as boxes in the UI font.
+                bundle.set_use_isolating(false);
+                if let Err(errors) = bundle.add_resource(resource) {
+                    log::error!("Errors adding messages for {}: {:?}", id, errors);
+                }
+                Some(bundle)
+            })
+            .collect();
+        Self {
+            language: language.to_string(),
+            bundles,
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        is_right_to_left(&self.language)
+    }
+
+    /// Formats the message `id` from the first language that has it, or returns the id.
+    pub fn format(&self, text: &LocalizedText) -> String {
+        let mut args = FluentArgs::new();
+        for (name, value) in &text.args {
+            let value = match value {
+                LocaleArg::Number(number) => FluentValue::from(*number),
+                LocaleArg::Text(text) => FluentValue::from(text.as_str()),
+            };
+            args.set(name.as_str(), value);
+        }
+
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle
+                .get_message(&text.id)
+                .and_then(|message| message.value())
+            else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let formatted = bundle.format_pattern(pattern, Some(&args), &mut errors);
+            if !errors.is_empty() {
+                log::warn!("Errors formatting {}: {:?}", text.id, errors);
+            }
+            return formatted.into_owned();
+        }
+        text.id.clone()
+    }
+
+    pub fn text(&self, id: &str) -> String {
+        self.format(&LocalizedText::new(id))
+    }
+}
+
+/// Systems writing [`LocalizedText`] into texts, for ordering systems that read the result.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocalizeText;
+
+/// Row of UI elements whose order is reversed while a right-to-left language is active.
+#[derive(Component)]
+pub struct MirrorRightToLeft;
+
+/// Fluent files of all supported languages, kept loaded for switching at runtime.
+#[derive(Resource)]
+struct LocaleFiles(Vec<(LanguageIdentifier, Handle<FluentSource>)>);
+
+impl FromWorld for LocaleFiles {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(
+            LANGUAGES
+                .iter()
+                .filter_map(|(language, _)| {
+                    let id = language.parse().ok()?;
+                    Some((id, asset_server.load(locale_path(language))))
+                })
+                .collect(),
+        )
+    }
+}
+
+pub fn i18n_plugin(app: &mut App) {
+    app.init_asset::<FluentSource>()
+        .init_asset_loader::<FluentLoader>()
+        .init_resource::<LocaleFiles>()
+        .init_resource::<Localization>()
+        .add_systems(Startup, preload_locales)
+        .add_systems(
+            Update,
+            update_localization.run_if(
+                resource_changed::<Settings>.or_else(on_event::<AssetEvent<FluentSource>>()),
+            ),
+        )
+        // After Update, so texts and rows spawned or changed there are laid out right away.
+        .add_systems(
+            PostUpdate,
+            (apply_localized_text.in_set(LocalizeText), mirror_layout).before(UiSystem::Layout),
+        );
+}
+
+fn preload_locales(files: Res<LocaleFiles>, mut preload: ResMut<Preload>) {
+    for (_, handle) in &files.0 {
+        preload.add(handle.clone());
+    }
+}
+
+fn update_localization(
+    settings: Res<Settings>,
+    files: Res<LocaleFiles>,
+    sources: Res<Assets<FluentSource>>,
+    mut assets_changed: EventReader<AssetEvent<FluentSource>>,
+    mut localization: ResMut<Localization>,
+) {
+    let reload = assets_changed.read().count() > 0;
+    if !reload && localization.language() == settings.language {
+        return;
+    }
+    *localization = Localization::new(&settings.language, |id| {
+        files
+            .0
+            .iter()
+            .find(|(language, _)| language == id)
+            .and_then(|(_, handle)| sources.get(handle))
+            .map(|source| source.0.as_str())
+    });
+}
+
+fn apply_localized_text(
+    localization: Res<Localization>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (localized, mut text) in texts.iter_mut() {
+        if !localization.is_changed() && !localized.is_changed() {
+            continue;
+        }
+        let value = localization.format(&localized);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn mirror_layout(
+    localization: Res<Localization>,
+    mut rows: Query<(Ref<MirrorRightToLeft>, &mut Style)>,
+) {
+    let direction = if localization.is_right_to_left() {
+        FlexDirection::RowReverse
+    } else {
+        FlexDirection::Row
+    };
+    for (mirror, mut style) in rows.iter_mut() {
+        if (localization.is_changed() || mirror.is_added()) && style.flex_direction != direction {
+            style.flex_direction = direction;
+        }
+    }
+}
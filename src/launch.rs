@@ -0,0 +1,221 @@
+use std::path::PathBuf;
+
+use bevy::{log, prelude::*, window::WindowMode};
+use konnektoren_core::game::{Game, GamePath};
+
+use crate::deep_link::query_pairs;
+
+/// Help text of the command line of the native build.
+pub const USAGE: &str = "\
+Usage: konnektoren-game [OPTIONS]
+
+Options:
+  --skip-intro        Go straight to the map, even if the intro was never shown
+  --challenge <ID>    Start the challenge with this id right after loading
+  --content <FILE>    Play the learning path in the JSON FILE instead of the bundled one
+  --profile <NAME>    Play with the profile NAME, which keeps its own progress
+  --lang <LANGUAGE>   Show the UI in LANGUAGE, e.g. de
+  --windowed          Run in a window
+  --fullscreen        Run in fullscreen
+  --seed <NUMBER>     Seed for anything random, to reproduce a run
+  --record <FILE>     Record the game actions to FILE instead of the config folder
+  --replay <FILE>     Play the game actions recorded in FILE
+  -h, --help          Print this help";
+
+/// How the game is started, from the command line of the native build or the page URL of
+/// the web build.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct LaunchOptions {
+    /// Goes from the splash screen to the map even if the intro was never shown.
+    pub skip_intro: bool,
+    /// Id of the challenge started once loading is done.
+    pub challenge: Option<String>,
+    /// JSON file of the learning path played instead of the bundled one.
+    pub content: Option<PathBuf>,
+    /// Name of the profile, each of which keeps its own progress.
+    pub profile: Option<String>,
+    /// Language identifier of the UI, e.g. `de`.
+    pub language: Option<String>,
+    pub window_mode: Option<WindowMode>,
+    pub seed: Option<u64>,
+    /// File the game actions are recorded to.
+    pub record: Option<PathBuf>,
+    /// File of recorded game actions that are played back.
+    pub replay: Option<PathBuf>,
+}
+
+impl LaunchOptions {
+    /// Parses command-line arguments, without the program name. Returns `None` if help was
+    /// asked for.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> anyhow::Result<Option<Self>> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow::anyhow!("{} needs a value", flag))
+            };
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--skip-intro" => options.skip_intro = true,
+                "--challenge" => options.challenge = Some(value()?),
+                "--content" => options.content = Some(value()?.into()),
+                "--profile" => options.profile = Some(profile_name(value()?)?),
+                "--lang" => options.language = Some(value()?),
+                "--windowed" => options.window_mode = Some(WindowMode::Windowed),
+                "--fullscreen" => options.window_mode = Some(WindowMode::BorderlessFullscreen),
+                "--seed" => {
+                    let seed = value()?;
+                    options.seed = Some(
+                        seed.parse()
+                            .map_err(|_| anyhow::anyhow!("Invalid seed {}", seed))?,
+                    );
+                }
+                "--record" => options.record = Some(value()?.into()),
+                "--replay" => options.replay = Some(value()?.into()),
+                _ => anyhow::bail!("Unknown option {}", flag),
+            }
+        }
+        Ok(Some(options))
+    }
+
+    /// Reads the parameters of a page URL, e.g. `?challenge=konnektoren-1&skip_intro=1&lang=de`.
+    /// Unknown parameters and invalid values are ignored, as links are shared by hand.
+    pub fn from_query(query: &str) -> Self {
+        let mut options = Self::default();
+        for (name, value) in query_pairs(query) {
+            match name.as_str() {
+                "challenge" if !value.is_empty() => options.challenge = Some(value),
+                "skip_intro" => options.skip_intro = !matches!(value.as_str(), "0" | "false"),
+                "lang" if !value.is_empty() => options.language = Some(value),
+                "profile" => match profile_name(value) {
+                    Ok(name) => options.profile = Some(name),
+                    Err(e) => log::warn!("{}", e),
+                },
+                _ => {}
+            }
+        }
+        options
+    }
+}
+
+impl LaunchOptions {
+    /// The game with the learning path of [`LaunchOptions::content`], or `None` to play the
+    /// bundled one.
+    pub fn game(&self) -> anyhow::Result<Option<Game>> {
+        let Some(path) = &self.content else {
+            return Ok(None);
+        };
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+        let game_path: GamePath = serde_json::from_str(&json)
+            .map_err(|e| anyhow::anyhow!("Invalid learning path {}: {}", path.display(), e))?;
+        Ok(Some(Game {
+            game_path,
+            ..default()
+        }))
+    }
+}
+
+/// Profile names become file names, so they are kept to letters, digits, `-` and `_`.
+fn profile_name(name: String) -> anyhow::Result<String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid profile name {}", name);
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> anyhow::Result<Option<LaunchOptions>> {
+        LaunchOptions::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_flags_with_separate_and_inline_values() {
+        let options = parse(&[
+            "--skip-intro",
+            "--challenge",
+            "konnektoren-1",
+            "--profile=tester",
+            "--fullscreen",
+            "--seed",
+            "42",
+            "--replay=crash.json",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            options,
+            LaunchOptions {
+                skip_intro: true,
+                challenge: Some("konnektoren-1".to_string()),
+                profile: Some("tester".to_string()),
+                window_mode: Some(WindowMode::BorderlessFullscreen),
+                seed: Some(42),
+                replay: Some("crash.json".into()),
+                ..default()
+            }
+        );
+    }
+
+    #[test]
+    fn reads_page_urls() {
+        assert_eq!(
+            LaunchOptions::from_query("?challenge=konnektoren-1&skip_intro=1&lang=de&x=y"),
+            LaunchOptions {
+                skip_intro: true,
+                challenge: Some("konnektoren-1".to_string()),
+                language: Some("de".to_string()),
+                ..default()
+            }
+        );
+        assert_eq!(
+            LaunchOptions::from_query("#skip_intro=0&profile=../other"),
+            LaunchOptions::default()
+        );
+    }
+
+    #[test]
+    fn loads_the_learning_path_given_as_content() {
+        assert_eq!(LaunchOptions::default().game().unwrap(), None);
+
+        let mut game_path = Game::default().game_path;
+        game_path.id = "custom".to_string();
+        game_path.challenges.truncate(1);
+        let file =
+            std::env::temp_dir().join(format!("konnektoren-path-{}.json", std::process::id()));
+        std::fs::write(&file, serde_json::to_string(&game_path).unwrap()).unwrap();
+        let options = parse(&["--content", file.to_str().unwrap()])
+            .unwrap()
+            .unwrap();
+        let game = options.game().unwrap().unwrap();
+        assert_eq!(game.game_path, game_path);
+
+        std::fs::write(&file, "{").unwrap();
+        assert!(options.game().is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert_eq!(parse(&["--help"]).unwrap(), None);
+        assert!(parse(&["--challenge"]).is_err());
+        assert!(parse(&["--record"]).is_err());
+        assert!(parse(&["--seed", "many"]).is_err());
+        assert!(parse(&["--profile", "../other"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+    }
+}
@@ -1,11 +1,28 @@
+pub mod accessibility;
 pub mod app_state;
+pub mod bridge;
+pub mod button;
+pub mod debug;
+pub mod deep_link;
+pub mod error;
 pub mod game;
 pub mod game_state;
+pub mod i18n;
+pub mod launch;
 pub mod map;
+pub mod plugin;
+pub mod preload;
+pub mod profile;
+pub mod random;
+pub mod replay;
 pub mod screen;
+pub mod settings;
 pub mod slides;
+pub mod slideshow;
 pub mod splash;
+pub mod theme;
+pub mod touch;
 
 pub mod prelude {
-    pub use crate::screen::despawn_screen;
+    pub use crate::{plugin::KonnektorenPlugin, screen::despawn_screen};
 }
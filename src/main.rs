@@ -1,16 +1,63 @@
-use bevy::{asset::AssetMetaCheck, prelude::*};
-use konnektoren_game::{
-    app_state::AppState, game::GamePlugin, game_state::GameState, map::map_plugin, screen,
-    slides::slides_plugin, splash::splash_plugin,
+use std::time::Duration;
+
+use bevy::{
+    asset::AssetMetaCheck,
+    prelude::*,
+    winit::{UpdateMode, WinitSettings},
 };
+use bevy_kira_audio::AudioPlugin;
+use konnektoren_game::{launch::LaunchOptions, plugin::KonnektorenPlugin};
+
+#[cfg(not(target_arch = "wasm32"))]
+fn launch_options() -> LaunchOptions {
+    match LaunchOptions::from_args(std::env::args().skip(1)) {
+        Ok(Some(mut options)) => {
+            // Keeps the last session, to attach to bug reports.
+            if options.record.is_none() {
+                options.record = konnektoren_game::replay::default_replay_file();
+            }
+            options
+        }
+        Ok(None) => {
+            println!("{}", konnektoren_game::launch::USAGE);
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, konnektoren_game::launch::USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Reads the options of a shared link from both the query and the hash of the page URL.
+#[cfg(target_arch = "wasm32")]
+fn launch_options() -> LaunchOptions {
+    let Some(location) = web_sys::window().map(|window| window.location()) else {
+        return LaunchOptions::default();
+    };
+    let query = location.search().unwrap_or_default();
+    let hash = location.hash().unwrap_or_default();
+    LaunchOptions::from_query(&format!("{}&{}", query, hash.trim_start_matches('#')))
+}
 
 fn main() {
+    let options = launch_options();
+    let mut konnektoren = KonnektorenPlugin::new();
+    match options.game() {
+        Ok(Some(game)) => konnektoren = konnektoren.with_game(game),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
     App::new()
         .insert_resource(AssetMetaCheck::Never) // fix for asset loading in wasm
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Konnektoren".to_string(),
                 resolution: (800.0, 600.0).into(),
+                mode: options.window_mode.unwrap_or_default(),
                 canvas: Some("#bevy".to_owned()),
                 // Tells wasm not to override default event handling, like F5 and Ctrl+R
                 prevent_default_event_handling: false,
@@ -18,9 +65,17 @@ fn main() {
             }),
             ..Default::default()
         }))
-        .insert_resource(GameState::default())
-        .init_state::<AppState>()
-        .add_systems(Startup, screen::setup)
-        .add_plugins((splash_plugin, slides_plugin, map_plugin, GamePlugin))
+        // Only redraw on input, so an idle game leaves the CPU alone; screens read by gamepad
+        // request redraws themselves while one is connected
+        .insert_resource(WinitSettings {
+            focused_mode: UpdateMode::Reactive {
+                wait: Duration::from_secs(1),
+            },
+            unfocused_mode: UpdateMode::ReactiveLowPower {
+                wait: Duration::from_secs(60),
+            },
+        })
+        .add_plugins(AudioPlugin)
+        .add_plugins(konnektoren.with_launch_options(options))
         .run();
 }
@@ -1,58 +1,232 @@
-use bevy::{log, prelude::*};
+use bevy::{
+    log,
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
 use konnektoren_core::challenges::ChallengeConfig;
 
-use crate::{app_state::AppState, game_state::GameState, prelude::despawn_screen};
+use crate::{
+    app_state::AppState,
+    button::{
+        pressed, set_toggle_label, spawn_button, ButtonKind, ButtonLabel, ButtonPresses, ButtonSpec,
+    },
+    game::events::GameAction,
+    game_state::GameState,
+    i18n::{LocalizedText, LANGUAGES},
+    prelude::despawn_screen,
+    profile::Profile,
+    screen::{window_size, TransitionTo},
+    settings::Settings,
+    theme::{
+        ColorVision, FontSize, ThemeColor, ThemeMode, ThemedBackground, ThemedText, UiTheme,
+        MIN_TARGET_SIZE, TEXT_SCALES, UI_SCALES,
+    },
+    touch::{Gesture, Pointer},
+};
 
-#[derive(Component)]
-struct OnMapScreen;
+/// Range the map can be zoomed in, relative to fitting the window.
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 4.0;
 
 pub fn map_plugin(app: &mut App) {
-    app.add_systems(OnEnter(AppState::Map), map_setup)
+    app.init_resource::<MapView>()
+        .init_resource::<TouchedChallenge>()
+        .add_systems(OnEnter(AppState::Map), map_setup)
         .add_systems(
             Update,
-            challenge_interaction_system.run_if(in_state(AppState::Map)),
+            (
+                (challenge_interaction_system, challenge_touch_system).chain(),
+                challenge_hover_system,
+                (pinch_and_pan_system, layout_map)
+                    .chain()
+                    .run_if(on_event::<Gesture>().or_else(on_event::<WindowResized>())),
+                theme_toggle_system,
+                language_toggle_system,
+                color_vision_toggle_system,
+                contrast_toggle_system,
+                text_size_toggle_system,
+                ui_scale_toggle_system,
+                motion_toggle_system,
+                replay_intro_system,
+            )
+                .run_if(in_state(AppState::Map)),
         )
         .add_systems(OnExit(AppState::Map), despawn_screen::<MapEntity>);
 }
 
+/// Root of the map screen; everything else on it is a descendant, despawned along with it.
 #[derive(Component)]
 struct MapEntity;
 
 #[derive(Component)]
-struct MapLine;
+pub struct ChallengeNode(pub (usize, String));
 
+/// Position of a challenge node in map cells, counted from the top left of the map.
 #[derive(Component)]
-struct ChallengeNode((usize, String));
+struct MapCell(Vec2);
 
-fn calculate_bounds(challenges: &[(String, i32, i32)]) -> ([i32; 2], [i32; 2]) {
-    let x_min = challenges
-        .iter()
-        .map(|(_, x, _)| *x)
-        .min_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(0);
-    let x_max = challenges
-        .iter()
-        .map(|(_, x, _)| *x)
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(0);
-    let y_min = challenges
+/// Size of the map in cells, fitted into the window at a zoom of 1.
+#[derive(Component)]
+struct MapArea(Vec2);
+
+/// Name and description of the challenge under the mouse, or held down on touch screens.
+#[derive(Component)]
+struct ChallengeDetails;
+
+/// Zoom and pan of the map, changed by pinching and dragging on touch screens.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct MapView {
+    pub zoom: f32,
+    /// Shift of the map from its fitted position, in logical pixels.
+    pub offset: Vec2,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+impl MapView {
+    /// Zooms by `factor` around `center`, keeping the point under it in place.
+    fn zoom_around(&mut self, center: Vec2, factor: f32) {
+        let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let factor = zoom / self.zoom;
+        self.offset = center - (center - self.offset) * factor;
+        self.zoom = zoom;
+    }
+}
+
+/// Challenge the current touch went down on. Touches start a challenge when released as a
+/// tap, so holding one can show its details instead.
+#[derive(Resource, Default)]
+struct TouchedChallenge(Option<String>);
+
+#[derive(Component)]
+struct ThemeToggle;
+
+#[derive(Component)]
+struct ReplayIntro;
+
+#[derive(Component)]
+struct LanguageToggle;
+
+#[derive(Component)]
+struct ColorVisionToggle;
+
+#[derive(Component)]
+struct ContrastToggle;
+
+#[derive(Component)]
+struct TextSizeToggle;
+
+#[derive(Component)]
+struct UiScaleToggle;
+
+#[derive(Component)]
+struct MotionToggle;
+
+/// Message id of the theme toggle, naming the theme it switches to.
+fn theme_toggle_label(mode: ThemeMode) -> &'static str {
+    match mode {
+        ThemeMode::Dark => "map-theme-light",
+        ThemeMode::Light => "map-theme-dark",
+    }
+}
+
+/// Message id of the colour vision toggle, naming the active palette.
+fn color_vision_label(vision: ColorVision) -> &'static str {
+    match vision {
+        ColorVision::Typical => "map-colors-typical",
+        ColorVision::RedGreen => "map-colors-red-green",
+        ColorVision::BlueYellow => "map-colors-blue-yellow",
+    }
+}
+
+fn next_color_vision(vision: ColorVision) -> ColorVision {
+    match vision {
+        ColorVision::Typical => ColorVision::RedGreen,
+        ColorVision::RedGreen => ColorVision::BlueYellow,
+        ColorVision::BlueYellow => ColorVision::Typical,
+    }
+}
+
+/// Message id of the contrast toggle, naming the contrast it switches to.
+fn contrast_toggle_label(high_contrast: bool) -> &'static str {
+    if high_contrast {
+        "map-contrast-normal"
+    } else {
+        "map-contrast-high"
+    }
+}
+
+/// Message id of the motion toggle, naming the motion it switches to.
+fn motion_toggle_label(reduced_motion: bool) -> &'static str {
+    if reduced_motion {
+        "map-motion-full"
+    } else {
+        "map-motion-reduced"
+    }
+}
+
+/// Label of the text size toggle, naming the active text size.
+fn text_size_label(text_scale: f32) -> LocalizedText {
+    LocalizedText::new("map-text-size").with_arg("percent", (text_scale * 100.0).round() as f64)
+}
+
+/// Label of the zoom toggle, naming the active zoom.
+fn ui_scale_label(ui_scale: f32) -> LocalizedText {
+    LocalizedText::new("map-zoom").with_arg("percent", (ui_scale * 100.0).round() as f64)
+}
+
+/// The scale after `current` in `scales`, wrapping around.
+fn next_scale(scales: &[f32], current: f32) -> f32 {
+    let index = scales
         .iter()
-        .map(|(_, _, y)| *y)
-        .min_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(0);
-    let y_max = challenges
+        .position(|scale| *scale == current)
+        .map_or(0, |index| (index + 1) % scales.len());
+    scales[index]
+}
+
+/// The language after `language` in [`LANGUAGES`], wrapping around.
+fn next_language(language: &str) -> &'static str {
+    let index = LANGUAGES
         .iter()
-        .map(|(_, _, y)| *y)
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .unwrap_or(0);
+        .position(|(id, _)| *id == language)
+        .map_or(0, |index| (index + 1) % LANGUAGES.len());
+    LANGUAGES[index].0
+}
 
-    ([x_min - 1, x_max + 2 * 1], [2 * y_min - 1, y_max + 4 * 1])
+/// Name of `language` in that language itself.
+fn language_name(language: &str) -> &str {
+    LANGUAGES
+        .iter()
+        .find(|(id, _)| *id == language)
+        .map_or(language, |(_, name)| name)
 }
 
-fn map_setup(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>) {
-    let window_width = 800.0; //window.width();
-    let window_height = 600.0; //window.height();
+fn calculate_bounds(challenges: &[(String, i32, i32)]) -> ([i32; 2], [i32; 2]) {
+    let x_min = challenges.iter().map(|(_, x, _)| *x).min().unwrap_or(0);
+    let x_max = challenges.iter().map(|(_, x, _)| *x).max().unwrap_or(0);
+    let y_min = challenges.iter().map(|(_, _, y)| *y).min().unwrap_or(0);
+    let y_max = challenges.iter().map(|(_, _, y)| *y).max().unwrap_or(0);
 
+    ([x_min - 1, x_max + 2], [2 * y_min - 1, y_max + 4])
+}
+
+fn map_setup(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    theme: Res<UiTheme>,
+    settings: Res<Settings>,
+    view: Res<MapView>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut touched: ResMut<TouchedChallenge>,
+) {
+    touched.0 = None;
     let challenges = game_state
         .game
         .game_path
@@ -68,34 +242,141 @@ fn map_setup(mut commands: Commands, game_state: Res<GameState>, asset_server: R
         .collect::<Vec<_>>();
 
     let (x_bounds, y_bounds) = calculate_bounds(&challenges);
-    let scale_x = window_width / (x_bounds[1] - x_bounds[0]) as f32;
-    let scale_y = window_height / (y_bounds[1] - y_bounds[0]) as f32;
-    let scale = scale_x.min(scale_y);
+    let area = Vec2::new(
+        (x_bounds[1] - x_bounds[0]) as f32,
+        (y_bounds[1] - y_bounds[0]) as f32,
+    );
+    let scale = map_scale(window_size(&windows), area) * view.zoom;
 
     let parent_entity = commands
-        .spawn(NodeBundle {
-            style: Style {
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::Center,
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: theme.palette.background.into(),
                 ..default()
             },
-            background_color: Color::rgb(0.1, 0.1, 0.1).into(),
-            ..default()
-        })
+            ThemedBackground(ThemeColor::Background),
+            MapArea(area),
+            MapEntity,
+        ))
         .id();
 
+    commands.entity(parent_entity).with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(theme.spacing.small),
+                    right: Val::Px(theme.spacing.small),
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::FlexEnd,
+                    max_width: Val::Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|toolbar| {
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new("map-replay-intro"))
+                        .with_font_size(FontSize::Medium),
+                    ReplayIntro,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::label(language_name(&settings.language))
+                        .with_font_size(FontSize::Medium),
+                    LanguageToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new(theme_toggle_label(theme.mode)))
+                        .with_font_size(FontSize::Medium),
+                    ThemeToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new(color_vision_label(
+                        settings.color_vision,
+                    )))
+                    .with_font_size(FontSize::Medium),
+                    ColorVisionToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new(contrast_toggle_label(
+                        settings.high_contrast,
+                    )))
+                    .with_font_size(FontSize::Medium),
+                    ContrastToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(text_size_label(settings.text_scale))
+                        .with_font_size(FontSize::Medium),
+                    TextSizeToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(ui_scale_label(settings.ui_scale))
+                        .with_font_size(FontSize::Medium),
+                    UiScaleToggle,
+                );
+                spawn_button(
+                    toolbar,
+                    &theme,
+                    ButtonSpec::localized(LocalizedText::new(motion_toggle_label(
+                        settings.reduced_motion,
+                    )))
+                    .with_font_size(FontSize::Medium),
+                    MotionToggle,
+                );
+            });
+    });
+
+    commands.entity(parent_entity).with_children(|parent| {
+        parent.spawn((
+            TextBundle {
+                text: Text::from_section("", theme.text_style(FontSize::Medium)),
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(theme.spacing.large),
+                    max_width: Val::Percent(90.0),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ThemedText(ThemeColor::Text, FontSize::Medium),
+            LocalizedText::new(""),
+            ChallengeDetails,
+        ));
+    });
+
     for (challenge_index, challenge) in game_state.game.game_path.challenges.iter().enumerate() {
         commands.entity(parent_entity).with_children(|parent| {
             add_challenge(
                 parent,
                 challenge,
                 challenge_index,
-                asset_server.load("fonts/FiraSans-Bold.ttf"),
+                &theme,
                 scale,
-                x_bounds,
-                y_bounds,
+                view.offset,
+                (x_bounds[0], y_bounds[0]),
             );
         });
     }
@@ -105,65 +386,302 @@ fn add_challenge(
     commands: &mut ChildBuilder,
     challenge: &ChallengeConfig,
     challenge_index: usize,
-    font: Handle<Font>,
+    theme: &UiTheme,
     scale: f32,
-    x_bounds: [i32; 2],
-    y_bounds: [i32; 2],
+    offset: Vec2,
+    origin: (i32, i32),
 ) {
-    let pos_x = ((challenge.position.unwrap_or_default().0 - x_bounds[0]) as f32) * scale;
-    let pos_y = ((challenge.position.unwrap_or_default().1 - y_bounds[0]) as f32) * scale;
+    let (x, y) = challenge.position.unwrap_or_default();
+    let cell = Vec2::new((x - origin.0) as f32, (y - origin.1) as f32);
 
-    commands
-        .spawn((
-            ButtonBundle {
-                style: Style {
-                    position_type: PositionType::Absolute,
-                    left: Val::Px(pos_x),
-                    top: Val::Px(pos_y),
-                    width: Val::Px(1.0 * scale),
-                    height: Val::Px(1.0 * scale),
-                    ..default()
-                },
-                background_color: Color::rgb(1.0, 0.0, 0.0).into(),
-                ..default()
-            },
+    spawn_button(
+        commands,
+        theme,
+        ButtonSpec::label(&challenge.name)
+            .with_kind(ButtonKind::MapNode)
+            .with_font_size(FontSize::Small)
+            .with_style(Style {
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::FlexStart,
+                margin: UiRect::ZERO,
+                padding: UiRect::ZERO,
+                ..node_style(theme, cell, scale, offset)
+            }),
+        (
             ChallengeNode((challenge_index, challenge.id.clone())),
-        ))
-        .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                &challenge.name,
-                TextStyle {
-                    font,
-                    font_size: 10.0,
-                    color: Color::WHITE,
-                },
-            ));
-        })
-        .insert(MapEntity);
+            MapCell(cell),
+        ),
+    );
+}
+
+/// Logical pixels per map cell for `area` cells to fit into `window`, in either orientation.
+fn map_scale(window: Vec2, area: Vec2) -> f32 {
+    (window.x / area.x).min(window.y / area.y)
+}
+
+/// Placement of the challenge node in `cell`, at least [`MIN_TARGET_SIZE`] in each direction.
+fn node_style(theme: &UiTheme, cell: Vec2, scale: f32, offset: Vec2) -> Style {
+    let position = offset + cell * scale;
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(position.x),
+        top: Val::Px(position.y),
+        width: Val::Px(scale),
+        // Larger text wraps onto more lines instead of being clipped.
+        min_height: Val::Px(scale.max(MIN_TARGET_SIZE)),
+        ..theme.button_style()
+    }
+}
+
+/// Places the challenge nodes for the current window size, zoom and pan.
+fn layout_map(
+    view: Res<MapView>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    areas: Query<&MapArea>,
+    mut nodes: Query<(&MapCell, &mut Style)>,
+) {
+    let Ok(area) = areas.get_single() else {
+        return;
+    };
+    let scale = map_scale(window_size(&windows), area.0) * view.zoom;
+    for (cell, mut style) in nodes.iter_mut() {
+        let position = view.offset + cell.0 * scale;
+        style.left = Val::Px(position.x);
+        style.top = Val::Px(position.y);
+        style.width = Val::Px(scale);
+        style.min_height = Val::Px(scale.max(MIN_TARGET_SIZE));
+    }
+}
+
+fn pinch_and_pan_system(mut gestures: EventReader<Gesture>, mut view: ResMut<MapView>) {
+    for gesture in gestures.read() {
+        match *gesture {
+            Gesture::Pinch { center, scale } => view.zoom_around(center, scale),
+            Gesture::Pan(delta) => view.offset += delta,
+            _ => {}
+        }
+    }
 }
 
+/// Starts a challenge when its node is pressed with the mouse or keyboard; touches only mark
+/// the node for [`challenge_touch_system`].
 fn challenge_interaction_system(
-    mut interaction_query: Query<
-        (&Interaction, &ChallengeNode),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut game_state: ResMut<GameState>,
-    mut app_state: ResMut<NextState<AppState>>,
+    presses: ButtonPresses<ChallengeNode>,
+    pointer: Res<Pointer>,
+    mut touched: ResMut<TouchedChallenge>,
+    mut actions: EventWriter<GameAction>,
 ) {
-    for (interaction, challenge_node) in interaction_query.iter_mut() {
-        match *interaction {
-            Interaction::Pressed => match game_state.game.create_challenge(&challenge_node.0 .1) {
-                Ok(challenge) => {
-                    game_state.challenge = challenge;
-                    app_state.set(AppState::Game);
-                    log::info!("Pressed challenge {}", challenge_node.0 .1);
-                }
-                Err(e) => {
-                    log::error!("Error creating challenge: {}", e);
-                }
-            },
-            Interaction::Hovered => {}
-            Interaction::None => {}
+    for challenge_node in pressed(&presses) {
+        let id = challenge_node.0 .1.clone();
+        if *pointer == Pointer::Touch {
+            touched.0 = Some(id);
+            continue;
+        }
+        log::info!("Pressed challenge {}", id);
+        actions.send(GameAction::StartChallenge(id));
+    }
+}
+
+/// A tap on a challenge node starts it and holding it shows its details; a tap anywhere
+/// else hides them. Any other gesture means the touch was not meant for the node.
+fn challenge_touch_system(
+    mut gestures: EventReader<Gesture>,
+    mut touched: ResMut<TouchedChallenge>,
+    game_state: Res<GameState>,
+    mut details: Query<(&mut LocalizedText, &mut Visibility), With<ChallengeDetails>>,
+    mut actions: EventWriter<GameAction>,
+) {
+    for gesture in gestures.read() {
+        let touched = touched.0.take();
+        match (gesture, touched) {
+            (Gesture::Tap(_), Some(id)) => {
+                log::info!("Tapped challenge {}", id);
+                actions.send(GameAction::StartChallenge(id));
+            }
+            (Gesture::Tap(_), None) => show_details(&mut details, None),
+            (Gesture::LongPress(_), Some(id)) => {
+                show_details(&mut details, challenge_config(&game_state, &id));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Shows the details of the challenge under the mouse while it hovers there.
+fn challenge_hover_system(
+    pointer: Res<Pointer>,
+    nodes: Query<(&Interaction, &ChallengeNode), Changed<Interaction>>,
+    game_state: Res<GameState>,
+    mut details: Query<(&mut LocalizedText, &mut Visibility), With<ChallengeDetails>>,
+) {
+    if *pointer == Pointer::Touch {
+        return;
+    }
+    for (interaction, node) in nodes.iter() {
+        let challenge = match interaction {
+            Interaction::Hovered => challenge_config(&game_state, &node.0 .1),
+            Interaction::Pressed | Interaction::None => None,
+        };
+        show_details(&mut details, challenge);
+    }
+}
+
+fn challenge_config<'a>(game_state: &'a GameState, id: &str) -> Option<&'a ChallengeConfig> {
+    game_state
+        .game
+        .game_path
+        .challenges
+        .iter()
+        .find(|challenge| challenge.id == id)
+}
+
+fn show_details(
+    details: &mut Query<(&mut LocalizedText, &mut Visibility), With<ChallengeDetails>>,
+    challenge: Option<&ChallengeConfig>,
+) {
+    for (mut text, mut visibility) in details.iter_mut() {
+        match challenge {
+            Some(challenge) => {
+                *text = LocalizedText::new("map-challenge-details")
+                    .with_arg("name", challenge.name.as_str())
+                    .with_arg("description", challenge.description.as_str());
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn theme_toggle_system(
+    presses: ButtonPresses<ThemeToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<ThemeToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.theme = match settings.theme {
+        ThemeMode::Dark => ThemeMode::Light,
+        ThemeMode::Light => ThemeMode::Dark,
+    };
+    set_toggle_label(
+        &toggles,
+        &mut labels,
+        LocalizedText::new(theme_toggle_label(settings.theme)),
+    );
+}
+
+fn color_vision_toggle_system(
+    presses: ButtonPresses<ColorVisionToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<ColorVisionToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.color_vision = next_color_vision(settings.color_vision);
+    set_toggle_label(
+        &toggles,
+        &mut labels,
+        LocalizedText::new(color_vision_label(settings.color_vision)),
+    );
+}
+
+fn contrast_toggle_system(
+    presses: ButtonPresses<ContrastToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<ContrastToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.high_contrast = !settings.high_contrast;
+    set_toggle_label(
+        &toggles,
+        &mut labels,
+        LocalizedText::new(contrast_toggle_label(settings.high_contrast)),
+    );
+}
+
+fn text_size_toggle_system(
+    presses: ButtonPresses<TextSizeToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<TextSizeToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.text_scale = next_scale(&TEXT_SCALES, settings.text_scale);
+    set_toggle_label(&toggles, &mut labels, text_size_label(settings.text_scale));
+}
+
+fn ui_scale_toggle_system(
+    presses: ButtonPresses<UiScaleToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<UiScaleToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.ui_scale = next_scale(&UI_SCALES, settings.ui_scale);
+    set_toggle_label(&toggles, &mut labels, ui_scale_label(settings.ui_scale));
+}
+
+fn motion_toggle_system(
+    presses: ButtonPresses<MotionToggle>,
+    mut labels: Query<&mut LocalizedText, With<ButtonLabel>>,
+    toggles: Query<&Children, With<MotionToggle>>,
+    mut settings: ResMut<Settings>,
+    mut profile: ResMut<Profile>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.reduced_motion = !settings.reduced_motion;
+    // Kept with the profile, so the choice outlasts the system preference it overrides.
+    profile.reduced_motion = Some(settings.reduced_motion);
+    set_toggle_label(
+        &toggles,
+        &mut labels,
+        LocalizedText::new(motion_toggle_label(settings.reduced_motion)),
+    );
+}
+
+fn language_toggle_system(
+    presses: ButtonPresses<LanguageToggle>,
+    mut labels: Query<&mut Text, With<ButtonLabel>>,
+    toggles: Query<&Children, With<LanguageToggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    if pressed(&presses).next().is_none() {
+        return;
+    }
+
+    settings.language = next_language(&settings.language).to_string();
+    for children in toggles.iter() {
+        let mut texts = labels.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = language_name(&settings.language).to_string();
         }
     }
 }
+
+fn replay_intro_system(
+    presses: ButtonPresses<ReplayIntro>,
+    mut transitions: EventWriter<TransitionTo>,
+) {
+    if pressed(&presses).next().is_some() {
+        transitions.send(TransitionTo(AppState::Slides));
+    }
+}
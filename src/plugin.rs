@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use bevy::{log, prelude::*};
+use konnektoren_core::game::Game;
+
+use crate::{
+    accessibility::accessibility_plugin,
+    app_state::AppState,
+    bridge::bridge_plugin,
+    button::button_plugin,
+    debug::debug_plugin,
+    deep_link::deep_link_plugin,
+    error::{error_plugin, ErrorRecovery, ErrorReport},
+    game::{
+        events::{ChallengeCompleted, ChallengeStarted},
+        start_challenge, GamePlugin,
+    },
+    game_state::GameState,
+    i18n::i18n_plugin,
+    launch::LaunchOptions,
+    map::map_plugin,
+    preload::preload_plugin,
+    profile::{profile_plugin, ProfileStore},
+    random::GameRng,
+    replay::{replay_plugin, Replay, ReplayPlayback, ReplayRecorder},
+    screen::{self, screen_plugin},
+    settings::{settings_plugin, Settings},
+    slides::slides_plugin,
+    slideshow::slideshow_plugin,
+    splash::splash_plugin,
+    theme::{theme_plugin, ThemeMode},
+    touch::touch_plugin,
+};
+
+/// Screens included besides the challenge player, which always is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Screens {
+    /// The splash screen, shown while assets load.
+    pub splash: bool,
+    /// The intro slides.
+    pub intro: bool,
+    /// The map, along with links and the page URL, which lead to it.
+    pub map: bool,
+}
+
+impl Screens {
+    pub const ALL: Self = Self {
+        splash: true,
+        intro: true,
+        map: true,
+    };
+
+    /// Just the challenge player, for apps that pick the challenge themselves.
+    pub const CHALLENGE_PLAYER: Self = Self {
+        splash: false,
+        intro: false,
+        map: false,
+    };
+}
+
+impl Default for Screens {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+type CompletionCallback = Arc<dyn Fn(&ChallengeCompleted) + Send + Sync>;
+
+#[derive(Resource, Default)]
+struct CompletionCallbacks(Vec<CompletionCallback>);
+
+/// The whole game, or parts of it, as a single plugin for apps embedding it.
+///
+/// The app brings the window, asset and audio plugins. Besides the callbacks given to
+/// [`KonnektorenPlugin::on_challenge_completed`], the app can read the events in
+/// [`crate::game::events`], such as [`ChallengeCompleted`].
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use konnektoren_game::plugin::KonnektorenPlugin;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(
+///         KonnektorenPlugin::challenge_player("konnektoren-1")
+///             .on_challenge_completed(|completed| println!("Scored {}%", completed.score)),
+///     )
+///     .run();
+/// ```
+#[derive(Default)]
+pub struct KonnektorenPlugin {
+    initial_state: Option<AppState>,
+    game: Option<Game>,
+    screens: Screens,
+    settings: Settings,
+    launch: LaunchOptions,
+    profile_store: Option<ProfileStore>,
+    without_camera: bool,
+    debug_tools: Option<bool>,
+    on_completed: Vec<CompletionCallback>,
+}
+
+impl KonnektorenPlugin {
+    /// The whole game, starting with the splash screen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only the challenge player, starting the challenge with the id `challenge`.
+    pub fn challenge_player(challenge: impl Into<String>) -> Self {
+        Self::new()
+            .with_screens(Screens::CHALLENGE_PLAYER)
+            .with_challenge(challenge)
+    }
+
+    /// Screen shown first, by default the first of the splash screen, the challenge asked
+    /// for, the map and the intro that is included.
+    pub fn with_initial_state(mut self, state: AppState) -> Self {
+        self.initial_state = Some(state);
+        self
+    }
+
+    /// Learning path and challenges played, instead of the bundled ones.
+    pub fn with_game(mut self, game: Game) -> Self {
+        self.game = Some(game);
+        self
+    }
+
+    pub fn with_screens(mut self, screens: Screens) -> Self {
+        self.screens = screens;
+        self
+    }
+
+    /// Starts the challenge with the id `challenge` once loading is done, or right away
+    /// without the splash screen.
+    pub fn with_challenge(mut self, challenge: impl Into<String>) -> Self {
+        self.launch.challenge = Some(challenge.into());
+        self
+    }
+
+    pub fn with_theme(mut self, theme: ThemeMode) -> Self {
+        self.settings.theme = theme;
+        self
+    }
+
+    /// Settings the game starts with; a language in the launch options takes precedence.
+    pub fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Options from the command line or page URL; a challenge given before is kept unless
+    /// the options name one.
+    pub fn with_launch_options(mut self, options: LaunchOptions) -> Self {
+        let challenge = options.challenge.clone().or(self.launch.challenge.take());
+        self.launch = LaunchOptions {
+            challenge,
+            ..options
+        };
+        self
+    }
+
+    /// Where progress is kept; a profile named in the launch options takes precedence.
+    pub fn with_profile_store(mut self, store: ProfileStore) -> Self {
+        self.profile_store = Some(store);
+        self
+    }
+
+    /// Leaves spawning the UI camera to the app.
+    pub fn without_camera(mut self) -> Self {
+        self.without_camera = true;
+        self
+    }
+
+    /// Includes the debug overlay and console, by default only in debug builds.
+    pub fn with_debug_tools(mut self, enabled: bool) -> Self {
+        self.debug_tools = Some(enabled);
+        self
+    }
+
+    /// Calls `callback` each time a challenge is completed.
+    pub fn on_challenge_completed(
+        mut self,
+        callback: impl Fn(&ChallengeCompleted) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_completed.push(Arc::new(callback));
+        self
+    }
+
+    fn initial_state(&self) -> AppState {
+        if let Some(state) = self.initial_state {
+            return state;
+        }
+        if self.screens.splash {
+            AppState::Splash
+        } else if self.launch.challenge.is_some() || !(self.screens.map || self.screens.intro) {
+            AppState::Game
+        } else if self.screens.map {
+            AppState::Map
+        } else {
+            AppState::Slides
+        }
+    }
+}
+
+impl Plugin for KonnektorenPlugin {
+    fn build(&self, app: &mut App) {
+        let mut game_state = GameState::default();
+        if let Some(game) = &self.game {
+            game_state.game = game.clone();
+        }
+        let mut settings = self.settings.clone();
+        if let Some(language) = &self.launch.language {
+            settings.language = language.clone();
+        }
+        let mut launch = self.launch.clone();
+        launch.skip_intro |= !self.screens.intro;
+        let mut record_file = launch.record.clone();
+        if let Some(path) = &self.launch.replay {
+            match Replay::load(path) {
+                Ok(replay) => {
+                    // Played back from the map, and not recorded over the replay of the session.
+                    launch.skip_intro = true;
+                    launch.challenge = None;
+                    launch.seed = launch.seed.or(replay.seed);
+                    record_file = None;
+                    app.insert_resource(ReplayPlayback::new(replay));
+                }
+                Err(e) => log::error!("Error loading replay {}: {}", path.display(), e),
+            }
+        }
+
+        // Before the plugins, so the profile asked for is the one that gets loaded.
+        if let Some(name) = &self.launch.profile {
+            app.insert_resource(ProfileStore::named(name));
+        } else if let Some(store) = &self.profile_store {
+            app.insert_resource(store.clone());
+        }
+        // Recorded even if it was not given, so every replay can be reproduced.
+        let rng = launch.seed.map_or_else(GameRng::default, GameRng::new);
+        let initial_state = self.initial_state();
+        let recovery = if self.screens.map {
+            Some(AppState::Map)
+        } else if self.screens.intro {
+            Some(AppState::Slides)
+        } else {
+            None
+        };
+        app.insert_resource(game_state)
+            .insert_resource(ErrorRecovery(recovery))
+            .insert_resource(settings)
+            .insert_resource(ReplayRecorder::new(rng.seed(), record_file))
+            .insert_resource(rng)
+            .insert_resource(launch)
+            .insert_resource(CompletionCallbacks(self.on_completed.clone()))
+            .insert_state(initial_state)
+            .add_systems(
+                Update,
+                call_completion_callbacks.run_if(on_event::<ChallengeCompleted>()),
+            );
+        if !self.without_camera {
+            app.add_systems(Startup, screen::setup);
+        }
+        // Without the splash screen nothing else starts the challenge asked for.
+        if initial_state == AppState::Game && !self.screens.splash {
+            app.add_systems(Startup, start_launch_challenge);
+        }
+
+        app.add_plugins((
+            preload_plugin,
+            settings_plugin,
+            screen_plugin,
+            i18n_plugin,
+            profile_plugin,
+            theme_plugin,
+            touch_plugin,
+            button_plugin,
+            accessibility_plugin,
+            error_plugin,
+            bridge_plugin,
+            replay_plugin,
+        ));
+        if self.screens.splash {
+            app.add_plugins(splash_plugin);
+        }
+        if self.screens.intro {
+            app.add_plugins((slideshow_plugin, slides_plugin));
+        }
+        if self.screens.map {
+            app.add_plugins((map_plugin, deep_link_plugin));
+        }
+        app.add_plugins(GamePlugin);
+        if self.debug_tools.unwrap_or(cfg!(debug_assertions)) {
+            app.add_plugins(debug_plugin);
+        }
+    }
+}
+
+/// Sets up the challenge asked for before the challenge player is first entered.
+fn start_launch_challenge(
+    launch: Res<LaunchOptions>,
+    mut game_state: ResMut<GameState>,
+    mut started: EventWriter<ChallengeStarted>,
+    mut errors: ResMut<ErrorReport>,
+) {
+    if let Some(id) = &launch.challenge {
+        start_challenge(id, &mut game_state, &mut started, &mut errors);
+    }
+}
+
+fn call_completion_callbacks(
+    mut completed: EventReader<ChallengeCompleted>,
+    callbacks: Res<CompletionCallbacks>,
+) {
+    for event in completed.read() {
+        for callback in &callbacks.0 {
+            callback(event);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::{asset::LoadState, prelude::*};
+
+/// Assets the splash screen loads before the game starts, so nothing pops in later.
+///
+/// Plugins add their assets at startup, or as soon as they know them, e.g. the images
+/// listed in a manifest once it has loaded.
+#[derive(Resource, Default)]
+pub struct Preload {
+    handles: Vec<UntypedHandle>,
+}
+
+impl Preload {
+    pub fn add(&mut self, handle: impl Into<UntypedHandle>) {
+        let handle = handle.into();
+        if !self.handles.contains(&handle) {
+            self.handles.push(handle);
+        }
+    }
+
+    pub fn progress(&self, asset_server: &AssetServer) -> LoadProgress {
+        let mut progress = LoadProgress {
+            total: self.handles.len(),
+            ..default()
+        };
+        for handle in &self.handles {
+            if asset_server.is_loaded_with_dependencies(handle.id()) {
+                progress.loaded += 1;
+            } else if let Some(LoadState::Failed) = asset_server.get_load_state(handle.id()) {
+                progress.failed.push(
+                    handle
+                        .path()
+                        .map(|path| path.to_string())
+                        .unwrap_or_else(|| format!("{:?}", handle.id())),
+                );
+            }
+        }
+        progress
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadProgress {
+    pub loaded: usize,
+    pub total: usize,
+    /// Paths of the assets that could not be loaded.
+    pub failed: Vec<String>,
+}
+
+impl LoadProgress {
+    /// Share of the assets loaded so far, from `0.0` to `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.loaded as f32 / self.total as f32
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.loaded == self.total
+    }
+}
+
+pub fn preload_plugin(app: &mut App) {
+    app.init_resource::<Preload>();
+}
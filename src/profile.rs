@@ -0,0 +1,133 @@
+use bevy::{log, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Progress of the player that is kept between launches.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Profile {
+    /// Whether the intro slides were shown once, so later launches go straight to the map.
+    pub intro_completed: bool,
+    /// Reduced motion as chosen on the map, or `None` to follow the system preference.
+    pub reduced_motion: Option<bool>,
+}
+
+/// Where the [`Profile`] is persisted.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub enum ProfileStore {
+    /// Not persisted, e.g. in tests.
+    Memory,
+    /// A JSON file on disk.
+    File(std::path::PathBuf),
+    /// An entry of the browser's local storage.
+    #[cfg(target_arch = "wasm32")]
+    LocalStorage(String),
+}
+
+impl Default for ProfileStore {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        dirs::config_dir()
+            .map(|dir| Self::File(dir.join("konnektoren").join("profile.json")))
+            .unwrap_or(Self::Memory)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        Self::LocalStorage("konnektoren.profile".to_string())
+    }
+}
+
+impl ProfileStore {
+    /// Store of the profile `name`, kept apart from the default one and from each other.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn named(name: &str) -> Self {
+        dirs::config_dir()
+            .map(|dir| {
+                Self::File(
+                    dir.join("konnektoren")
+                        .join("profiles")
+                        .join(format!("{}.json", name)),
+                )
+            })
+            .unwrap_or(Self::Memory)
+    }
+
+    /// Store of the profile `name`, kept apart from the default one and from each other.
+    #[cfg(target_arch = "wasm32")]
+    pub fn named(name: &str) -> Self {
+        Self::LocalStorage(format!("konnektoren.profile.{}", name))
+    }
+
+    /// Reads the stored profile, falling back to a fresh one if there is none yet.
+    pub fn load(&self) -> anyhow::Result<Profile> {
+        let Some(json) = self.read()? else {
+            return Ok(Profile::default());
+        };
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, profile: &Profile) -> anyhow::Result<()> {
+        self.write(&serde_json::to_string_pretty(profile)?)
+    }
+
+    fn read(&self) -> anyhow::Result<Option<String>> {
+        match self {
+            Self::Memory => Ok(None),
+            Self::File(path) => match std::fs::read_to_string(path) {
+                Ok(json) => Ok(Some(json)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            #[cfg(target_arch = "wasm32")]
+            Self::LocalStorage(key) => Ok(local_storage()?
+                .get_item(key)
+                .map_err(|_| anyhow::anyhow!("Cannot read {} from local storage", key))?),
+        }
+    }
+
+    fn write(&self, json: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Memory => Ok(()),
+            Self::File(path) => {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                Ok(std::fs::write(path, json)?)
+            }
+            #[cfg(target_arch = "wasm32")]
+            Self::LocalStorage(key) => local_storage()?
+                .set_item(key, json)
+                .map_err(|_| anyhow::anyhow!("Cannot write {} to local storage", key)),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> anyhow::Result<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| anyhow::anyhow!("Local storage is not available"))
+}
+
+pub fn profile_plugin(app: &mut App) {
+    let store = app
+        .world
+        .get_resource_or_insert_with(ProfileStore::default)
+        .clone();
+    let profile = store.load().unwrap_or_else(|e| {
+        log::error!("Error loading profile: {}", e);
+        Profile::default()
+    });
+    app.insert_resource(profile)
+        .add_systems(PostUpdate, save_profile.run_if(resource_changed::<Profile>));
+}
+
+fn save_profile(profile: Res<Profile>, store: Res<ProfileStore>) {
+    // A freshly loaded profile has nothing new to write.
+    if profile.is_added() {
+        return;
+    }
+    if let Err(e) = store.save(&profile) {
+        log::error!("Error saving profile: {}", e);
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+/// Source of anything random in the game. Its seed is recorded with replays and can be given
+/// with `--seed`, so a run can be reproduced.
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    seed: u64,
+    rng: fastrand::Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    /// Seed the generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rng(&mut self) -> &mut fastrand::Rng {
+        &mut self.rng
+    }
+}
+
+impl Default for GameRng {
+    /// Seeded at random, for runs that need not be reproduced.
+    fn default() -> Self {
+        Self::new(fastrand::u64(..))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_numbers() {
+        let mut a = GameRng::new(42);
+        let mut b = GameRng::new(42);
+        let numbers =
+            |rng: &mut GameRng| -> Vec<u32> { (0..8).map(|_| rng.rng().u32(..)).collect() };
+        assert_eq!(numbers(&mut a), numbers(&mut b));
+        assert_eq!(a.seed(), 42);
+    }
+}
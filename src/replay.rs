@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+use std::time::Duration;
+
+use bevy::{core::FrameCount, log, prelude::*, time::TimeUpdateStrategy, window::RequestRedraw};
+use konnektoren_core::game::Game;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    game::{events::GameAction, CommandsPlugin, ExecuteActions},
+    game_state::GameState,
+    screen::TransitionTo,
+};
+
+/// Game action executed during a recorded session.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReplayEntry {
+    /// Frame the action was executed in.
+    pub frame: u32,
+    /// Seconds since launch when the action was executed.
+    pub seconds: f32,
+    pub action: GameAction,
+}
+
+/// Every game action of a session, to reproduce it later.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Replay {
+    /// Version of the game that recorded the replay.
+    pub game_version: String,
+    /// Learning path played, see [`content_version`].
+    pub content_version: String,
+    /// Seed of the [`crate::random::GameRng`] of the session, if it was recorded.
+    pub seed: Option<u64>,
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Plays the replay on a fresh game state of `game`, without screens or a window, and
+    /// returns the state it ends in.
+    pub fn run_headless(&self, game: Game) -> GameState {
+        let mut game_state = GameState::default();
+        game_state.game = game;
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, CommandsPlugin))
+            // A second per update, so long pauses between actions take few updates.
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)))
+            .add_event::<TransitionTo>()
+            .add_event::<RequestRedraw>()
+            .insert_resource(game_state)
+            .insert_resource(ReplayPlayback::new(self.clone()))
+            .add_systems(Update, play_replay);
+        while !app.world.resource::<ReplayPlayback>().is_finished() {
+            app.update();
+        }
+        app.world
+            .remove_resource::<GameState>()
+            .expect("game state is never removed")
+    }
+}
+
+/// Id and checksum of the learning path of `game`, so a replay of other content stands out.
+pub fn content_version(game: &Game) -> String {
+    let json = serde_json::to_string(&game.game_path).unwrap_or_default();
+    // FNV-1a, which unlike the std hashers gives the same value in every build.
+    let checksum = json.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{}-{:016x}", game.game_path.id, checksum)
+}
+
+/// File the native build records the last session to, unless told otherwise.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_replay_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("konnektoren").join("replay.json"))
+}
+
+/// Records the game actions of the session.
+#[derive(Resource, Debug, Default)]
+pub struct ReplayRecorder {
+    pub replay: Replay,
+    /// Written after every action, so the replay survives a crash; kept in memory if `None`.
+    pub file: Option<PathBuf>,
+}
+
+impl ReplayRecorder {
+    pub fn new(seed: u64, file: Option<PathBuf>) -> Self {
+        Self {
+            replay: Replay {
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+                seed: Some(seed),
+                ..default()
+            },
+            file,
+        }
+    }
+}
+
+/// Sends the actions of a replay with the same time between them as recorded, starting once
+/// loading is done.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    next: usize,
+    /// Seconds since launch when playback started.
+    started: Option<f32>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            started: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.entries.len()
+    }
+}
+
+pub fn replay_plugin(app: &mut App) {
+    app.init_resource::<ReplayRecorder>()
+        .add_systems(
+            PostUpdate,
+            record_actions
+                .before(ExecuteActions)
+                .run_if(on_event::<GameAction>()),
+        )
+        .add_systems(
+            Update,
+            (
+                check_content_version.run_if(resource_added::<ReplayPlayback>),
+                play_replay.run_if(resource_exists::<ReplayPlayback>),
+            )
+                .chain(),
+        );
+}
+
+/// Saves before the actions are executed, so the action that crashes the game is in the file.
+fn record_actions(
+    mut actions: EventReader<GameAction>,
+    frame: Res<FrameCount>,
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let recorder = &mut *recorder;
+    recorder.replay.content_version = content_version(&game_state.game);
+    for action in actions.read() {
+        recorder.replay.entries.push(ReplayEntry {
+            frame: frame.0,
+            seconds: time.elapsed_seconds(),
+            action: action.clone(),
+        });
+    }
+    if let Some(file) = &recorder.file {
+        if let Err(e) = recorder.replay.save(file) {
+            log::error!("Error saving replay: {}", e);
+        }
+    }
+}
+
+fn check_content_version(playback: Res<ReplayPlayback>, game_state: Res<GameState>) {
+    let current = content_version(&game_state.game);
+    if playback.replay.content_version != current {
+        log::warn!(
+            "Replay recorded with content {}, playing with {}",
+            playback.replay.content_version,
+            current
+        );
+    }
+}
+
+/// Timed by the recorded seconds rather than frames, as a reactive event loop only updates
+/// on input, which a replay does not have.
+fn play_replay(
+    time: Res<Time>,
+    state: Option<Res<State<AppState>>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut actions: EventWriter<GameAction>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    if playback.is_finished() {
+        return;
+    }
+    // Keeps the event loop running until the last action is sent.
+    redraw.send(RequestRedraw);
+    if state.is_some_and(|state| *state.get() == AppState::Splash) {
+        return;
+    }
+    let Some(first) = playback.replay.entries.first().map(|entry| entry.seconds) else {
+        return;
+    };
+    let started = *playback.started.get_or_insert(time.elapsed_seconds());
+    let elapsed = time.elapsed_seconds() - started;
+    while let Some(entry) = playback.replay.entries.get(playback.next) {
+        if elapsed < entry.seconds - first {
+            break;
+        }
+        actions.send(entry.action.clone());
+        playback.next += 1;
+    }
+}
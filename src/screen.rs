@@ -1,14 +1,68 @@
 use bevy::{
-    core_pipeline::core_2d::Camera2dBundle,
-    ecs::{
-        component::Component,
-        entity::Entity,
-        query::With,
-        system::{Commands, Query},
-    },
-    hierarchy::DespawnRecursiveExt,
+    prelude::*,
+    ui::FocusPolicy,
+    window::{PrimaryWindow, RequestRedraw},
 };
 
+use crate::{app_state::AppState, settings::Settings, theme::UiTheme};
+
+/// How long fading out of, or into, a screen takes.
+const FADE_SECONDS: f32 = 0.25;
+
+/// Requests a change of [`AppState`], fading out of the current screen and into the next.
+#[derive(Event, Clone, Copy, Eq, PartialEq, Debug)]
+pub struct TransitionTo(pub AppState);
+
+#[derive(Resource, Default, Debug)]
+enum ScreenTransition {
+    #[default]
+    Idle,
+    FadingOut {
+        target: AppState,
+        timer: Timer,
+    },
+    FadingIn(Timer),
+}
+
+impl ScreenTransition {
+    /// How much the overlay covers the screen, from `0.0` to `1.0`.
+    fn opacity(&self) -> f32 {
+        match self {
+            Self::Idle => 0.0,
+            Self::FadingOut { timer, .. } => timer.fraction(),
+            Self::FadingIn(timer) => 1.0 - timer.fraction(),
+        }
+    }
+}
+
+/// Full-screen node drawn over every screen while a transition runs.
+#[derive(Component)]
+pub struct TransitionOverlay;
+
+/// Window size assumed when there is no window to measure, e.g. in headless runs.
+pub const FALLBACK_WINDOW_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+
+/// Size of the primary window in logical pixels.
+pub fn window_size(windows: &Query<&Window, With<PrimaryWindow>>) -> Vec2 {
+    windows
+        .get_single()
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .unwrap_or(FALLBACK_WINDOW_SIZE)
+}
+
+/// Size of the primary window in physical pixels, e.g. to pick an image resolution.
+pub fn physical_window_size(windows: &Query<&Window, With<PrimaryWindow>>) -> Vec2 {
+    windows
+        .get_single()
+        .map(|window| {
+            Vec2::new(
+                window.physical_width() as f32,
+                window.physical_height() as f32,
+            )
+        })
+        .unwrap_or(FALLBACK_WINDOW_SIZE)
+}
+
 pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
     for entity in &to_despawn {
         commands.entity(entity).despawn_recursive();
@@ -18,3 +72,107 @@ pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut comm
 pub fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
+
+pub fn screen_plugin(app: &mut App) {
+    app.add_event::<TransitionTo>()
+        .init_resource::<ScreenTransition>()
+        .add_systems(Startup, spawn_overlay)
+        // After Update, so requests sent by screens take effect on the next frame.
+        .add_systems(PostUpdate, (start_transition, fade).chain());
+}
+
+fn spawn_overlay(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        TransitionOverlay,
+    ));
+}
+
+fn start_transition(
+    mut requests: EventReader<TransitionTo>,
+    settings: Res<Settings>,
+    mut transition: ResMut<ScreenTransition>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for TransitionTo(target) in requests.read() {
+        if matches!(*transition, ScreenTransition::FadingOut { target: current, .. } if current == *target)
+        {
+            continue;
+        }
+        if settings.reduced_motion {
+            app_state.set(*target);
+            *transition = ScreenTransition::Idle;
+            continue;
+        }
+        // Start from the current cover, so a request during a transition does not flash.
+        let mut timer = Timer::from_seconds(FADE_SECONDS, TimerMode::Once);
+        timer.set_elapsed(timer.duration().mul_f32(transition.opacity()));
+        *transition = ScreenTransition::FadingOut {
+            target: *target,
+            timer,
+        };
+    }
+}
+
+fn fade(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    theme: Res<UiTheme>,
+    mut transition: ResMut<ScreenTransition>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut overlays: Query<
+        (&mut BackgroundColor, &mut Visibility, &mut FocusPolicy),
+        With<TransitionOverlay>,
+    >,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    match &mut *transition {
+        ScreenTransition::Idle => {}
+        ScreenTransition::FadingOut { target, timer } => {
+            if timer.tick(time.delta()).finished() || settings.reduced_motion {
+                app_state.set(*target);
+                *transition =
+                    ScreenTransition::FadingIn(Timer::from_seconds(FADE_SECONDS, TimerMode::Once));
+            }
+        }
+        ScreenTransition::FadingIn(timer) => {
+            if timer.tick(time.delta()).finished() || settings.reduced_motion {
+                *transition = ScreenTransition::Idle;
+            }
+        }
+    }
+
+    let idle = matches!(*transition, ScreenTransition::Idle);
+    for (mut background, mut visibility, mut focus) in overlays.iter_mut() {
+        let color = theme.palette.clear.with_a(transition.opacity());
+        if background.0 != color {
+            background.0 = color;
+        }
+        let (shown, policy) = if idle {
+            (Visibility::Hidden, FocusPolicy::Pass)
+        } else {
+            // Keep presses from reaching the screen that is fading away.
+            (Visibility::Inherited, FocusPolicy::Block)
+        };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+        if *focus != policy {
+            *focus = policy;
+        }
+    }
+    if !idle {
+        redraw.send(RequestRedraw);
+    }
+}
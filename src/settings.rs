@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::{
+    i18n::DEFAULT_LANGUAGE,
+    profile::Profile,
+    theme::{ColorVision, ThemeMode},
+};
+
+/// Player preferences that can be changed while the game is running.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub theme: ThemeMode,
+    pub color_vision: ColorVision,
+    /// Pure black and white surroundings with stronger button and focus colours.
+    pub high_contrast: bool,
+    /// Zoom of the whole UI, applied through [`UiScale`].
+    pub ui_scale: f32,
+    /// Factor on every font size, e.g. `2.0` for text at 200%.
+    pub text_scale: f32,
+    /// Replaces animations with instant changes.
+    pub reduced_motion: bool,
+    /// Language identifier of the UI, e.g. `de` or `de-AT`.
+    pub language: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: ThemeMode::default(),
+            color_vision: ColorVision::default(),
+            high_contrast: false,
+            ui_scale: 1.0,
+            text_scale: 1.0,
+            reduced_motion: false,
+            language: DEFAULT_LANGUAGE.to_string(),
+        }
+    }
+}
+
+pub fn settings_plugin(app: &mut App) {
+    app.init_resource::<Settings>()
+        .add_systems(Startup, restore_reduced_motion);
+}
+
+/// Takes reduced motion from the player's choice if there is one, or else from the system.
+fn restore_reduced_motion(profile: Res<Profile>, mut settings: ResMut<Settings>) {
+    settings.reduced_motion = profile
+        .reduced_motion
+        .unwrap_or(settings.reduced_motion || prefers_reduced_motion());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn prefers_reduced_motion() -> bool {
+    false
+}
+
+/// Whether the browser asks for reduced motion, e.g. set in the accessibility settings of the
+/// operating system.
+#[cfg(target_arch = "wasm32")]
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|query| query.matches())
+}
@@ -1,57 +1,209 @@
-use crate::{app_state::AppState, prelude::despawn_screen};
-use bevy::prelude::*;
-
-const SLIDES: [&str; 6] = [
-    "slides/1.png",
-    "slides/3.png",
-    "slides/6.png",
-    "slides/7.png",
-    "slides/8.png",
-    "slides/9.png",
-];
+use crate::{
+    app_state::AppState,
+    button::{pressed, spawn_button, ButtonPresses, ButtonSpec},
+    i18n::{Localization, LocalizedText, MirrorRightToLeft},
+    prelude::despawn_screen,
+    profile::Profile,
+    screen::{physical_window_size, TransitionTo},
+    settings::Settings,
+    slideshow::{IntroSlideshow, Slide, Slideshow},
+    theme::{FontSize, ThemeColor, ThemedBackground, ThemedText, UiTheme, MIN_TARGET_SIZE},
+    touch::Gesture,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    input::gamepad::{GamepadButton, GamepadButtonType},
+    prelude::*,
+    window::{PrimaryWindow, RequestRedraw, WindowResized},
+};
+use bevy_kira_audio::prelude::{Audio, AudioControl};
 
 #[derive(Component)]
 struct SlideshowScreen;
 
-#[derive(Resource, Deref, DerefMut)]
-struct SlideImage(usize);
+/// Share of the window width taken up by the slide image.
+const SLIDE_WIDTH_PERCENT: f32 = 75.0;
+
+/// How long moving from one slide to the next takes.
+const TRANSITION_SECONDS: f32 = 0.4;
+
+/// How far, in logical pixels, a slide moves while fading in or out.
+const TRANSITION_DISTANCE: f32 = 60.0;
+
+#[derive(Resource, Default)]
+struct CurrentSlide {
+    index: usize,
+    /// Whether the slide was reached by moving forward, which decides the transition direction.
+    forward: bool,
+}
+
+/// Counts down the duration of the current slide, if it has one.
+#[derive(Resource, Default)]
+struct AutoAdvance(Option<Timer>);
+
+/// What a slide button, key, swipe or gamepad button does.
+#[derive(Component, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum SlideAction {
+    Previous,
+    Next,
+    Skip,
+}
+
+#[derive(Event, Clone, Copy, Eq, PartialEq, Debug)]
+pub struct NavigateSlides(pub SlideAction);
+
+/// The image showing the current slide.
+#[derive(Component)]
+struct SlideView;
+
+/// Fades and moves the slide view out, swaps in `next` halfway, then brings it back in.
+#[derive(Component)]
+struct SlideTransition {
+    timer: Timer,
+    /// `1.0` when slides leave to the left, `-1.0` when they leave to the right.
+    direction: f32,
+    next: Handle<Image>,
+}
+
+impl SlideTransition {
+    /// Starts a transition to `next`, picking up at the opacity the view currently has so
+    /// that interrupting a running transition does not flash.
+    fn new(next: Handle<Image>, leftward: bool, opacity: f32) -> Self {
+        let mut timer = Timer::from_seconds(TRANSITION_SECONDS, TimerMode::Once);
+        timer.set_elapsed(timer.duration().mul_f32((1.0 - opacity) / 2.0));
+        Self {
+            timer,
+            direction: if leftward { 1.0 } else { -1.0 },
+            next,
+        }
+    }
+
+    /// Opacity and horizontal offset of the view at the current point of the transition.
+    fn frame(&self) -> (f32, f32) {
+        let progress = self.timer.fraction();
+        if progress < 0.5 {
+            let out = progress * 2.0;
+            (1.0 - out, -self.direction * out * TRANSITION_DISTANCE)
+        } else {
+            let remaining = 2.0 - progress * 2.0;
+            (
+                1.0 - remaining,
+                self.direction * remaining * TRANSITION_DISTANCE,
+            )
+        }
+    }
+}
+
+#[derive(Component)]
+struct SlideCaption;
+
+/// Row holding one [`SlideDot`] per slide.
+#[derive(Component)]
+struct SlideDots;
+
+/// A dot of the slide indicator, showing the slide with the given index.
+#[derive(Component)]
+pub struct SlideDot(pub usize);
+
+/// The intro slideshow, once its manifest has loaded.
+#[derive(SystemParam)]
+struct IntroSlides<'w> {
+    slideshows: Res<'w, Assets<Slideshow>>,
+    intro: Res<'w, IntroSlideshow>,
+}
+
+impl IntroSlides<'_> {
+    fn slides(&self) -> &[Slide] {
+        self.slideshows
+            .get(&self.intro.0)
+            .map(|slideshow| slideshow.slides.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Loads the slide image variants that fit the primary window.
+#[derive(SystemParam)]
+pub(crate) struct SlideImages<'w, 's> {
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl SlideImages<'_, '_> {
+    pub(crate) fn best_fit(&self, slide: &Slide) -> Option<Handle<Image>> {
+        let window_width = physical_window_size(&self.windows).x;
+        let variant = slide.best_variant(window_width * SLIDE_WIDTH_PERCENT / 100.0)?;
+        Some(self.asset_server.load(&variant.path))
+    }
+}
+
+/// Whether another slide came up, or the manifest was (re)loaded.
+fn slide_changed() -> impl Condition<()> {
+    resource_changed::<CurrentSlide>.or_else(on_event::<AssetEvent<Slideshow>>())
+}
 
 pub fn slides_plugin(app: &mut App) {
-    app.add_systems(OnEnter(AppState::Slides), setup_slideshow)
-        .add_systems(Update, navigate_slides.run_if(in_state(AppState::Slides)))
-        .add_systems(OnExit(AppState::Slides), despawn_screen::<SlideshowScreen>);
+    app.add_event::<NavigateSlides>()
+        .init_resource::<CurrentSlide>()
+        .init_resource::<AutoAdvance>()
+        .add_systems(OnEnter(AppState::Slides), setup_slideshow)
+        .add_systems(
+            Update,
+            (
+                (
+                    button_navigation,
+                    keyboard_navigation,
+                    swipe_navigation,
+                    gamepad_navigation,
+                    auto_advance,
+                ),
+                navigate_slides,
+                (
+                    start_slide.run_if(slide_changed()),
+                    spawn_dots.run_if(slide_changed()),
+                    show_caption.run_if(slide_changed()),
+                    show_slide.run_if(slide_changed().or_else(on_event::<WindowResized>())),
+                ),
+                (update_dots, animate_transition),
+            )
+                .chain()
+                .run_if(in_state(AppState::Slides)),
+        )
+        .add_systems(
+            Update,
+            poll_gamepads.run_if(in_state(AppState::Slides).and_then(any_gamepad_connected)),
+        )
+        .add_systems(
+            OnExit(AppState::Slides),
+            (
+                despawn_screen::<SlideshowScreen>,
+                stop_narration,
+                complete_intro,
+            ),
+        );
 }
 
-fn create_button(commands: &mut ChildBuilder, text: &str, font: Handle<Font>) {
-    commands
-        .spawn(ButtonBundle {
-            style: Style {
-                width: Val::Px(100.0),
-                height: Val::Px(40.0),
-                margin: UiRect::all(Val::Px(5.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                padding: UiRect::all(Val::Px(5.0)),
-                ..default()
-            },
-            background_color: Color::rgb(0.25, 0.25, 0.25).into(),
-            ..default()
-        })
-        .with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                text,
-                TextStyle {
-                    font,
-                    font_size: 20.0,
-                    color: Color::WHITE,
-                },
-            ));
-        });
+fn create_button(commands: &mut ChildBuilder, text: &str, action: SlideAction, theme: &UiTheme) {
+    spawn_button(
+        commands,
+        theme,
+        ButtonSpec::localized(LocalizedText::new(text)).with_style(Style {
+            min_width: Val::Px(100.0),
+            min_height: Val::Px(MIN_TARGET_SIZE),
+            ..theme.button_style()
+        }),
+        action,
+    );
 }
 
-fn setup_slideshow(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let first_slide = asset_server.load("slides/1.png");
+fn dot_color(theme: &UiTheme, active: bool) -> Color {
+    if active {
+        theme.palette.text
+    } else {
+        theme.palette.button.normal
+    }
+}
 
+fn setup_slideshow(mut commands: Commands, theme: Res<UiTheme>) {
     commands
         .spawn((
             NodeBundle {
@@ -60,6 +212,7 @@ fn setup_slideshow(mut commands: Commands, asset_server: Res<AssetServer>) {
                     height: Val::Percent(100.0),
                     flex_direction: FlexDirection::ColumnReverse,
                     justify_content: JustifyContent::FlexStart,
+                    padding: UiRect::bottom(Val::Px(theme.spacing.large * 3.0 + MIN_TARGET_SIZE)),
                     ..default()
                 },
                 background_color: Color::NONE.into(),
@@ -68,92 +221,406 @@ fn setup_slideshow(mut commands: Commands, asset_server: Res<AssetServer>) {
             SlideshowScreen,
         ))
         .with_children(|parent| {
-            parent.spawn((
-                ImageBundle {
+            parent
+                .spawn(NodeBundle {
                     style: Style {
-                        width: Val::Px(600.0),
-                        height: Val::Px(400.0),
+                        width: Val::Percent(100.0),
                         margin: UiRect::all(Val::Auto),
-                        flex_shrink: 0.0,
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(theme.spacing.small * 2.0),
                         ..default()
                     },
-                    image: UiImage::new(first_slide),
                     ..default()
-                },
-                SlideshowScreen,
-            ));
+                })
+                .with_children(|slide| {
+                    slide.spawn((
+                        ImageBundle {
+                            style: Style {
+                                width: Val::Percent(SLIDE_WIDTH_PERCENT),
+                                aspect_ratio: Some(16.0 / 9.0),
+                                flex_shrink: 0.0,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        SlideView,
+                    ));
+                    slide.spawn((
+                        TextBundle::from_section("", theme.text_style(FontSize::Medium))
+                            .with_text_justify(JustifyText::Center),
+                        ThemedText(ThemeColor::Text, FontSize::Medium),
+                        LocalizedText::new(""),
+                        SlideCaption,
+                    ));
+                });
 
-            parent
-                .spawn(NodeBundle {
+            parent.spawn((
+                NodeBundle {
                     style: Style {
                         width: Val::Percent(100.0),
-                        height: Val::Px(40.0),
                         position_type: PositionType::Absolute,
-                        bottom: Val::Px(20.0),
-                        flex_direction: FlexDirection::Row,
-                        justify_content: JustifyContent::SpaceAround,
-                        align_items: AlignItems::Center,
-                        align_self: AlignSelf::End,
+                        bottom: Val::Px(theme.spacing.large * 2.0 + MIN_TARGET_SIZE),
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(theme.spacing.small * 2.0),
                         ..default()
                     },
-                    background_color: Color::rgb(0.2, 0.2, 0.2).into(),
                     ..default()
-                })
+                },
+                SlideDots,
+                MirrorRightToLeft,
+            ));
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            position_type: PositionType::Absolute,
+                            bottom: Val::Px(theme.spacing.large),
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            justify_content: JustifyContent::SpaceAround,
+                            align_items: AlignItems::Center,
+                            align_self: AlignSelf::End,
+                            ..default()
+                        },
+                        background_color: theme.palette.surface.into(),
+                        ..default()
+                    },
+                    ThemedBackground(ThemeColor::Surface),
+                    MirrorRightToLeft,
+                ))
                 .with_children(|buttons| {
-                    create_button(
-                        buttons,
-                        "Previous",
-                        asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    );
-                    create_button(
-                        buttons,
-                        "Next",
-                        asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    );
-                    create_button(
-                        buttons,
-                        "Skip",
-                        asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    );
+                    create_button(buttons, "slides-previous", SlideAction::Previous, &theme);
+                    create_button(buttons, "slides-next", SlideAction::Next, &theme);
+                    create_button(buttons, "slides-skip", SlideAction::Skip, &theme);
                 });
         });
 
-    commands.insert_resource(SlideImage(0));
+    commands.insert_resource(CurrentSlide::default());
+    commands.insert_resource(AutoAdvance::default());
+}
+
+fn button_navigation(
+    presses: ButtonPresses<SlideAction>,
+    mut navigate: EventWriter<NavigateSlides>,
+) {
+    for action in pressed(&presses) {
+        navigate.send(NavigateSlides(*action));
+    }
+}
+
+fn keyboard_navigation(
+    keys: Res<ButtonInput<KeyCode>>,
+    localization: Res<Localization>,
+    mut navigate: EventWriter<NavigateSlides>,
+) {
+    // Arrows point the way the slides are read.
+    let (back, forward) = if localization.is_right_to_left() {
+        (KeyCode::ArrowRight, KeyCode::ArrowLeft)
+    } else {
+        (KeyCode::ArrowLeft, KeyCode::ArrowRight)
+    };
+    for key in keys.get_just_pressed() {
+        let action = match *key {
+            key if key == back => SlideAction::Previous,
+            key if key == forward || key == KeyCode::Space => SlideAction::Next,
+            KeyCode::Escape => SlideAction::Skip,
+            _ => continue,
+        };
+        navigate.send(NavigateSlides(action));
+    }
+}
+
+fn swipe_navigation(
+    mut gestures: EventReader<Gesture>,
+    localization: Res<Localization>,
+    mut navigate: EventWriter<NavigateSlides>,
+) {
+    for gesture in gestures.read() {
+        let Gesture::Swipe(distance) = *gesture else {
+            continue;
+        };
+        if distance.x.abs() < distance.y.abs() {
+            continue;
+        }
+        // Swiping against the reading direction moves on.
+        let action = if (distance.x < 0.0) != localization.is_right_to_left() {
+            SlideAction::Next
+        } else {
+            SlideAction::Previous
+        };
+        navigate.send(NavigateSlides(action));
+    }
+}
+
+fn gamepad_navigation(
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    mut navigate: EventWriter<NavigateSlides>,
+) {
+    for gamepad in gamepads.iter() {
+        let bindings = [
+            (GamepadButtonType::DPadLeft, SlideAction::Previous),
+            (GamepadButtonType::LeftTrigger, SlideAction::Previous),
+            (GamepadButtonType::DPadRight, SlideAction::Next),
+            (GamepadButtonType::RightTrigger, SlideAction::Next),
+            (GamepadButtonType::South, SlideAction::Next),
+            (GamepadButtonType::Start, SlideAction::Skip),
+        ];
+        for (button_type, action) in bindings {
+            if buttons.just_pressed(GamepadButton::new(gamepad, button_type)) {
+                navigate.send(NavigateSlides(action));
+            }
+        }
+    }
+}
+
+fn any_gamepad_connected(gamepads: Res<Gamepads>) -> bool {
+    gamepads.iter().next().is_some()
+}
+
+/// Gamepad input does not wake a reactive event loop, so it keeps updating while a gamepad
+/// could navigate the slides.
+fn poll_gamepads(mut redraw: EventWriter<RequestRedraw>) {
+    redraw.send(RequestRedraw);
+}
+
+fn auto_advance(
+    time: Res<Time>,
+    mut auto_advance: ResMut<AutoAdvance>,
+    mut navigate: EventWriter<NavigateSlides>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    let Some(timer) = auto_advance.0.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).finished() {
+        auto_advance.0 = None;
+        navigate.send(NavigateSlides(SlideAction::Next));
+    } else {
+        redraw.send(RequestRedraw);
+    }
 }
 
 fn navigate_slides(
-    mut slide_image_res: ResMut<SlideImage>,
-    mut interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
-    mut text_query: Query<&Text>,
-    mut state: ResMut<NextState<AppState>>,
+    mut current: ResMut<CurrentSlide>,
+    mut navigate: EventReader<NavigateSlides>,
+    mut transitions: EventWriter<TransitionTo>,
+    intro: IntroSlides,
+) {
+    let count = intro.slides().len();
+    for NavigateSlides(action) in navigate.read() {
+        match action {
+            SlideAction::Skip => {
+                transitions.send(TransitionTo(AppState::Map));
+                return;
+            }
+            _ if count == 0 => {}
+            SlideAction::Previous => {
+                current.index = (current.index + count - 1) % count;
+                current.forward = false;
+            }
+            SlideAction::Next => {
+                current.index = (current.index + 1) % count;
+                current.forward = true;
+            }
+        }
+    }
+}
+
+/// Restarts the auto-advance timer and the narration for the slide that just came up.
+fn start_slide(
+    current: Res<CurrentSlide>,
+    intro: IntroSlides,
+    mut auto_advance: ResMut<AutoAdvance>,
+    audio: Option<Res<Audio>>,
     asset_server: Res<AssetServer>,
-    mut image_query: Query<&mut UiImage, With<SlideshowScreen>>,
 ) {
-    for (interaction, children) in interaction_query.iter_mut() {
-        if *interaction == Interaction::Pressed {
-            if let Ok(text) = text_query.get_mut(children[0]) {
-                match text.sections[0].value.as_str() {
-                    "Previous" => {
-                        if slide_image_res.0 > 0 {
-                            slide_image_res.0 -= 1;
-                        } else {
-                            slide_image_res.0 = SLIDES.len() - 1;
-                        }
-                    }
-                    "Next" => {
-                        slide_image_res.0 = (slide_image_res.0 + 1) % SLIDES.len();
-                    }
-                    "Skip" => {
-                        state.set(AppState::Map);
-                        return;
-                    }
-                    _ => {}
-                }
+    let slides = intro.slides();
+    let Some(slide) = slides.get(current.index) else {
+        return;
+    };
 
-                if let Ok(mut ui_image) = image_query.get_single_mut() {
-                    *ui_image = UiImage::new(asset_server.load(SLIDES[slide_image_res.0]));
-                }
+    // The last slide waits for the player instead of wrapping around.
+    let is_last = current.index + 1 == slides.len();
+    auto_advance.0 = slide
+        .duration
+        .filter(|_| !is_last)
+        .map(|seconds| Timer::from_seconds(seconds, TimerMode::Once));
+
+    if let Some(audio) = audio {
+        audio.stop();
+        if let Some(narration) = &slide.narration {
+            audio.play(asset_server.load(narration));
+        }
+    }
+}
+
+fn complete_intro(mut profile: ResMut<Profile>) {
+    if !profile.intro_completed {
+        profile.intro_completed = true;
+    }
+}
+
+fn stop_narration(audio: Option<Res<Audio>>) {
+    if let Some(audio) = audio {
+        audio.stop();
+    }
+}
+
+/// Shows the image variant of the current slide that best fits the window, with a
+/// transition if another slide came up.
+fn show_slide(
+    mut commands: Commands,
+    current: Res<CurrentSlide>,
+    intro: IntroSlides,
+    settings: Res<Settings>,
+    localization: Res<Localization>,
+    slide_images: SlideImages,
+    mut images: Query<
+        (
+            Entity,
+            &mut UiImage,
+            &BackgroundColor,
+            Option<&mut SlideTransition>,
+        ),
+        With<SlideView>,
+    >,
+) {
+    let Some(slide) = intro.slides().get(current.index) else {
+        return;
+    };
+
+    let Some(texture) = slide_images.best_fit(slide) else {
+        return;
+    };
+
+    for (entity, mut image, tint, transition) in images.iter_mut() {
+        let shown = transition
+            .as_ref()
+            .map_or(&image.texture, |transition| &transition.next);
+        if *shown == texture {
+            continue;
+        }
+
+        // Only moving between slides animates; the first slide and resizes swap in place.
+        let animate = current.is_changed()
+            && !settings.reduced_motion
+            && image.texture != UiImage::default().texture;
+        match transition {
+            _ if animate => {
+                // Slides move against the reading direction when moving forward.
+                let leftward = current.forward != localization.is_right_to_left();
+                commands.entity(entity).insert(SlideTransition::new(
+                    texture.clone(),
+                    leftward,
+                    tint.0.a(),
+                ));
             }
+            Some(mut transition) => transition.next = texture.clone(),
+            None => image.texture = texture.clone(),
+        }
+    }
+}
+
+fn animate_transition(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut views: Query<(
+        Entity,
+        &mut SlideTransition,
+        &mut UiImage,
+        &mut BackgroundColor,
+        &mut Style,
+    )>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    for (entity, mut transition, mut image, mut tint, mut style) in views.iter_mut() {
+        if settings.reduced_motion {
+            let duration = transition.timer.duration();
+            transition.timer.set_elapsed(duration);
+        }
+        transition.timer.tick(time.delta());
+
+        if transition.timer.fraction() >= 0.5 && image.texture != transition.next {
+            image.texture = transition.next.clone();
         }
+
+        if transition.timer.finished() {
+            tint.0.set_a(1.0);
+            style.left = Val::Auto;
+            commands.entity(entity).remove::<SlideTransition>();
+        } else {
+            let (opacity, offset) = transition.frame();
+            tint.0.set_a(opacity);
+            style.left = Val::Px(offset);
+            redraw.send(RequestRedraw);
+        }
+    }
+}
+
+fn show_caption(
+    current: Res<CurrentSlide>,
+    intro: IntroSlides,
+    mut captions: Query<&mut LocalizedText, With<SlideCaption>>,
+) {
+    let Some(slide) = intro.slides().get(current.index) else {
+        return;
+    };
+    for mut caption in captions.iter_mut() {
+        if caption.id != slide.caption {
+            *caption = LocalizedText::new(slide.caption.clone());
+        }
+    }
+}
+
+/// Keeps one indicator dot per slide, which changes only when the manifest is (re)loaded.
+fn spawn_dots(
+    mut commands: Commands,
+    current: Res<CurrentSlide>,
+    intro: IntroSlides,
+    theme: Res<UiTheme>,
+    rows: Query<(Entity, Option<&Children>), With<SlideDots>>,
+) {
+    let count = intro.slides().len();
+    for (row, dots) in rows.iter() {
+        if dots.map_or(0, |dots| dots.len()) == count {
+            continue;
+        }
+        commands
+            .entity(row)
+            .despawn_descendants()
+            .with_children(|dots| {
+                for index in 0..count {
+                    dots.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(10.0),
+                                height: Val::Px(10.0),
+                                ..default()
+                            },
+                            background_color: dot_color(&theme, index == current.index).into(),
+                            ..default()
+                        },
+                        SlideDot(index),
+                    ));
+                }
+            });
+    }
+}
+
+fn update_dots(
+    current: Res<CurrentSlide>,
+    theme: Res<UiTheme>,
+    mut dots: Query<(&SlideDot, &mut BackgroundColor)>,
+) {
+    if !current.is_changed() && !theme.is_changed() {
+        return;
+    }
+    for (dot, mut background) in dots.iter_mut() {
+        *background = dot_color(&theme, dot.0 == current.index).into();
     }
 }
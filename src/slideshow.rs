@@ -0,0 +1,153 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+    window::WindowResized,
+};
+use bevy_kira_audio::prelude::{Audio, AudioSource};
+use serde::Deserialize;
+
+use crate::{preload::Preload, slides::SlideImages};
+
+/// Manifest of the intro slides, shipped next to the slide images.
+pub const INTRO_SLIDESHOW: &str = "slides/intro.slides.json";
+
+/// One resolution of a slide image.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct SlideVariant {
+    pub path: String,
+    /// Width of the image in pixels.
+    pub width: u32,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Slide {
+    pub images: Vec<SlideVariant>,
+    /// Message id of the caption, shown as is if no language has such a message.
+    #[serde(default)]
+    pub caption: String,
+    /// Audio played while the slide is shown.
+    #[serde(default)]
+    pub narration: Option<String>,
+    /// Seconds after which the slideshow moves on by itself.
+    #[serde(default)]
+    pub duration: Option<f32>,
+}
+
+impl Slide {
+    /// The smallest image at least `width` pixels wide, or the largest one if none is.
+    pub fn best_variant(&self, width: f32) -> Option<&SlideVariant> {
+        self.images
+            .iter()
+            .filter(|variant| variant.width as f32 >= width)
+            .min_by_key(|variant| variant.width)
+            .or_else(|| self.images.iter().max_by_key(|variant| variant.width))
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Clone, Debug, PartialEq)]
+pub struct Slideshow {
+    pub slides: Vec<Slide>,
+}
+
+/// Handle to the intro slideshow, loaded at startup so it is ready when the slides show.
+#[derive(Resource, Deref)]
+pub struct IntroSlideshow(pub Handle<Slideshow>);
+
+impl FromWorld for IntroSlideshow {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource::<AssetServer>().load(INTRO_SLIDESHOW))
+    }
+}
+
+#[derive(Default)]
+pub struct SlideshowLoader;
+
+impl AssetLoader for SlideshowLoader {
+    type Asset = Slideshow;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Slideshow, anyhow::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(serde_json::from_slice(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["slides.json"]
+    }
+}
+
+pub fn slideshow_plugin(app: &mut App) {
+    app.init_asset::<Slideshow>()
+        .init_asset_loader::<SlideshowLoader>()
+        .init_resource::<IntroSlideshow>()
+        .add_systems(Startup, preload_manifest)
+        .add_systems(
+            Update,
+            // A resized window may need other image sizes, which load ahead of the slides too.
+            preload_slides
+                .run_if(on_event::<AssetEvent<Slideshow>>().or_else(on_event::<WindowResized>())),
+        );
+}
+
+fn preload_manifest(intro: Res<IntroSlideshow>, mut preload: ResMut<Preload>) {
+    preload.add(intro.0.clone());
+}
+
+/// Adds the images that fit the window and the narration of the intro to the preloaded
+/// assets once its manifest is known.
+fn preload_slides(
+    intro: Res<IntroSlideshow>,
+    slideshows: Res<Assets<Slideshow>>,
+    asset_server: Res<AssetServer>,
+    slide_images: SlideImages,
+    audio: Option<Res<Audio>>,
+    mut preload: ResMut<Preload>,
+) {
+    let Some(slideshow) = slideshows.get(&intro.0) else {
+        return;
+    };
+    for slide in &slideshow.slides {
+        if let Some(image) = slide_images.best_fit(slide) {
+            preload.add(image);
+        }
+        // Narration can only be loaded when audio is available.
+        if let (Some(narration), Some(_)) = (&slide.narration, &audio) {
+            preload.add(asset_server.load::<AudioSource>(narration));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(path: &str, width: u32) -> SlideVariant {
+        SlideVariant {
+            path: path.to_string(),
+            width,
+        }
+    }
+
+    #[test]
+    fn picks_smallest_variant_that_fills_the_width() {
+        let slide = Slide {
+            images: vec![variant("large.png", 1920), variant("small.png", 480)],
+            caption: String::new(),
+            narration: None,
+            duration: None,
+        };
+        assert_eq!(slide.best_variant(400.0).unwrap().path, "small.png");
+        assert_eq!(slide.best_variant(600.0).unwrap().path, "large.png");
+        assert_eq!(slide.best_variant(4000.0).unwrap().path, "large.png");
+    }
+}
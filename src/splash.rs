@@ -1,10 +1,36 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, log, prelude::*, window::RequestRedraw};
 
-use crate::{app_state::AppState, prelude::despawn_screen};
+use crate::{
+    app_state::AppState,
+    button::{pressed, spawn_button, ButtonPresses, ButtonSpec},
+    game::events::GameAction,
+    game_state::GameState,
+    i18n::LocalizedText,
+    launch::LaunchOptions,
+    preload::{LoadProgress, Preload},
+    prelude::despawn_screen,
+    profile::Profile,
+    screen::TransitionTo,
+    theme::{FontSize, ThemeColor, ThemedBackground, ThemedText, UiTheme},
+};
+
+/// Shortest time the splash screen is shown, even if everything loads instantly.
+const MINIMUM_DURATION: f32 = 1.0;
 
 pub fn splash_plugin(app: &mut App) {
-    app.add_systems(OnEnter(AppState::Splash), splash_setup)
-        .add_systems(Update, countdown.run_if(in_state(AppState::Splash)))
+    app.init_resource::<LaunchOptions>()
+        .init_resource::<NextScreenRequested>()
+        .add_systems(OnEnter(AppState::Splash), splash_setup)
+        .add_systems(
+            Update,
+            (
+                update_progress,
+                show_load_error,
+                finish_loading,
+                continue_system,
+            )
+                .run_if(in_state(AppState::Splash)),
+        )
         .add_systems(OnExit(AppState::Splash), despawn_screen::<OnSplashScreen>);
 }
 
@@ -14,20 +40,47 @@ struct OnSplashScreen;
 #[derive(Resource, Deref, DerefMut)]
 struct SplashTimer(Timer);
 
-fn splash_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// Whether the next screen was requested during this visit of the splash screen, so a
+/// challenge is only started once.
+#[derive(Resource, Default)]
+struct NextScreenRequested(bool);
+
+/// The filled part of the progress bar.
+#[derive(Component)]
+struct ProgressFill;
+
+#[derive(Component)]
+struct ProgressBar;
+
+/// Dismisses the loading error and starts the game with whatever did load.
+#[derive(Component)]
+struct ContinueButton;
+
+fn splash_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<UiTheme>,
+    mut preload: ResMut<Preload>,
+) {
     let icon = asset_server.load("logo.png");
+    preload.add(icon.clone());
+
     commands
         .spawn((
             NodeBundle {
                 style: Style {
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(theme.spacing.large),
                     width: Val::Percent(100.0),
                     height: Val::Percent(100.0),
                     ..default()
                 },
+                background_color: theme.palette.background.into(),
                 ..default()
             },
+            ThemedBackground(ThemeColor::Background),
             OnSplashScreen,
         ))
         .with_children(|parent| {
@@ -39,16 +92,159 @@ fn splash_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 image: UiImage::new(icon),
                 ..default()
             });
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(200.0),
+                            height: Val::Px(8.0),
+                            ..default()
+                        },
+                        background_color: theme.palette.surface.into(),
+                        ..default()
+                    },
+                    ThemedBackground(ThemeColor::Surface),
+                    ProgressBar,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: theme.palette.text.into(),
+                            ..default()
+                        },
+                        ThemedBackground(ThemeColor::Text),
+                        ProgressFill,
+                    ));
+                });
+        });
+    commands.insert_resource(SplashTimer(Timer::from_seconds(
+        MINIMUM_DURATION,
+        TimerMode::Once,
+    )));
+    commands.insert_resource(NextScreenRequested::default());
+}
+
+/// Where the splash screen leads once loading is done: the challenge asked for at launch,
+/// or else the intro or the map.
+#[derive(SystemParam)]
+struct NextScreen<'w> {
+    profile: Res<'w, Profile>,
+    launch: Res<'w, LaunchOptions>,
+    game_state: Res<'w, GameState>,
+    transitions: EventWriter<'w, TransitionTo>,
+    actions: EventWriter<'w, GameAction>,
+    requested: ResMut<'w, NextScreenRequested>,
+}
+
+impl NextScreen<'_> {
+    fn request(&mut self) {
+        if std::mem::replace(&mut self.requested.0, true) {
+            return;
+        }
+        if let Some(id) = &self.launch.challenge {
+            let exists = self
+                .game_state
+                .game
+                .game_path
+                .challenges
+                .iter()
+                .any(|challenge| challenge.id == *id);
+            if exists {
+                self.actions.send(GameAction::StartChallenge(id.clone()));
+                return;
+            }
+            log::error!("No challenge with id {}, showing the map instead", id);
+        }
+        let skip_intro = self.launch.skip_intro || self.launch.challenge.is_some();
+        let state = if self.profile.intro_completed || skip_intro {
+            AppState::Map
+        } else {
+            AppState::Slides
+        };
+        self.transitions.send(TransitionTo(state));
+    }
+}
+
+/// What the splash screen needs to know about the assets being loaded.
+#[derive(SystemParam)]
+struct Loading<'w> {
+    preload: Res<'w, Preload>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl Loading<'_> {
+    fn progress(&self) -> LoadProgress {
+        self.preload.progress(&self.asset_server)
+    }
+}
+
+fn update_progress(loading: Loading, mut fills: Query<&mut Style, With<ProgressFill>>) {
+    let width = Val::Percent(loading.progress().fraction() * 100.0);
+    for mut fill in fills.iter_mut() {
+        if fill.width != width {
+            fill.width = width;
+        }
+    }
+}
+
+/// Replaces the progress bar with the assets that failed to load.
+fn show_load_error(
+    mut commands: Commands,
+    loading: Loading,
+    theme: Res<UiTheme>,
+    bars: Query<(Entity, &Parent), With<ProgressBar>>,
+) {
+    let failed = loading.progress().failed;
+    if failed.is_empty() {
+        return;
+    }
+    for (bar, screen) in bars.iter() {
+        log::error!("Error loading assets: {}", failed.join(", "));
+        commands.entity(bar).despawn_recursive();
+        commands.entity(screen.get()).with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("", theme.text_style(FontSize::Medium)),
+                ThemedText(ThemeColor::Text, FontSize::Medium),
+                LocalizedText::new("splash-load-error")
+                    .with_arg("count", failed.len())
+                    .with_arg("files", failed.join(", ")),
+            ));
+            spawn_button(
+                parent,
+                &theme,
+                ButtonSpec::localized(LocalizedText::new("splash-continue")),
+                ContinueButton,
+            );
         });
-    commands.insert_resource(SplashTimer(Timer::from_seconds(1.0, TimerMode::Once)));
+    }
 }
 
-fn countdown(
-    mut app_state: ResMut<NextState<AppState>>,
+fn finish_loading(
+    mut next_screen: NextScreen,
     time: Res<Time>,
     mut timer: ResMut<SplashTimer>,
+    mut redraw: EventWriter<RequestRedraw>,
+    loading: Loading,
 ) {
-    if timer.tick(time.delta()).finished() {
-        app_state.set(AppState::Slides);
+    let progress = loading.progress();
+    if !progress.failed.is_empty() {
+        return;
+    }
+    if timer.tick(time.delta()).finished() && progress.is_complete() {
+        next_screen.request();
+    } else {
+        redraw.send(RequestRedraw);
+    }
+}
+
+fn continue_system(presses: ButtonPresses<ContinueButton>, mut next_screen: NextScreen) {
+    if pressed(&presses).next().is_some() {
+        next_screen.request();
     }
 }
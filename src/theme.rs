@@ -0,0 +1,544 @@
+use bevy::{
+    asset::{AssetLoadFailedEvent, LoadState},
+    prelude::*,
+};
+use unic_langid::LanguageIdentifier;
+
+use crate::{preload::Preload, settings::Settings};
+
+/// Bundled font covering Latin, Greek and Cyrillic scripts.
+const FIRA_SANS: &str = "fonts/FiraSans-Bold.ttf";
+
+/// Bundled font that also covers Arabic and Hebrew scripts, so it backs up every stack.
+const DEJAVU_SANS: &str = "fonts/DejaVuSans-Bold.ttf";
+
+/// Font of marks such as check and cross, which the text fonts may lack.
+const SYMBOL_FONT: &str = DEJAVU_SANS;
+
+/// Fonts tried in order for languages the default stack does not suit, by base language.
+const FONT_STACKS: [(&str, &[&str]); 3] = [
+    ("ar", &[DEJAVU_SANS]),
+    ("fa", &[DEJAVU_SANS]),
+    ("he", &[DEJAVU_SANS]),
+];
+
+/// Fonts tried in order for all other languages.
+const DEFAULT_FONT_STACK: &[&str] = &[FIRA_SANS, DEJAVU_SANS];
+
+/// Paths of the fonts tried in order for `language`.
+pub fn font_stack(language: &str) -> &'static [&'static str] {
+    let base = language
+        .parse::<LanguageIdentifier>()
+        .map(|id| id.language.as_str().to_string())
+        .unwrap_or_default();
+    FONT_STACKS
+        .iter()
+        .find(|(language, _)| *language == base)
+        .map_or(DEFAULT_FONT_STACK, |(_, stack)| stack)
+}
+
+/// Handles to every bundled font, loaded at startup so switching languages is instant.
+#[derive(Resource, Debug)]
+pub struct Fonts(Vec<(&'static str, Handle<Font>)>);
+
+impl Fonts {
+    /// The first font in the stack of `language` that has not failed to load.
+    pub fn for_language(&self, language: &str, asset_server: &AssetServer) -> Handle<Font> {
+        let stack: Vec<&Handle<Font>> = font_stack(language)
+            .iter()
+            .filter_map(|path| {
+                self.0
+                    .iter()
+                    .find(|(font, _)| font == path)
+                    .map(|(_, handle)| handle)
+            })
+            .collect();
+        stack
+            .iter()
+            .find(|handle| {
+                !matches!(
+                    asset_server.get_load_state(handle.id()),
+                    Some(LoadState::Failed)
+                )
+            })
+            .or(stack.first())
+            .map(|handle| (*handle).clone())
+            .unwrap_or_default()
+    }
+
+    pub fn symbols(&self) -> Handle<Font> {
+        self.0
+            .iter()
+            .find(|(font, _)| *font == SYMBOL_FONT)
+            .map(|(_, handle)| handle.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl FromWorld for Fonts {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let mut fonts: Vec<(&'static str, Handle<Font>)> = Vec::new();
+        let stacks = FONT_STACKS.iter().map(|(_, stack)| *stack);
+        for path in std::iter::once(DEFAULT_FONT_STACK).chain(stacks).flatten() {
+            if !fonts.iter().any(|(font, _)| font == path) {
+                fonts.push((path, asset_server.load(*path)));
+            }
+        }
+        Self(fonts)
+    }
+}
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// Colour vision the palette is adjusted for, so correct and incorrect never differ only
+/// in a hue the player cannot tell apart.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash)]
+pub enum ColorVision {
+    #[default]
+    Typical,
+    /// Protanopia and deuteranopia: red and green look alike.
+    RedGreen,
+    /// Tritanopia: blue and yellow look alike.
+    BlueYellow,
+}
+
+/// Palette entry used by [`ThemedBackground`] and [`ThemedText`] to follow theme switches.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ThemeColor {
+    Background,
+    Surface,
+    Text,
+    ButtonText,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ButtonColors {
+    pub normal: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+    pub disabled: Color,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub clear: Color,
+    pub background: Color,
+    pub surface: Color,
+    pub text: Color,
+    pub button: ButtonColors,
+    pub button_text: Color,
+    pub map_node: ButtonColors,
+    pub focus: Color,
+    /// Border of buttons without focus, so their shape shows without relying on fill colour.
+    pub outline: Color,
+    pub correct: Color,
+    pub incorrect: Color,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        Self {
+            clear: Color::rgb_u8(43, 44, 47),
+            background: Color::rgb(0.1, 0.1, 0.1),
+            surface: Color::rgb(0.2, 0.2, 0.2),
+            text: Color::WHITE,
+            button: ButtonColors {
+                normal: Color::rgb(0.25, 0.25, 0.25),
+                hovered: Color::rgb(0.35, 0.35, 0.35),
+                pressed: Color::rgb(0.15, 0.15, 0.15),
+                disabled: Color::rgb(0.18, 0.18, 0.18),
+            },
+            button_text: Color::WHITE,
+            map_node: ButtonColors {
+                normal: Color::rgb(1.0, 0.0, 0.0),
+                hovered: Color::rgb(1.0, 0.3, 0.3),
+                pressed: Color::rgb(0.7, 0.0, 0.0),
+                disabled: Color::rgb(0.4, 0.2, 0.2),
+            },
+            focus: Color::rgb(1.0, 0.85, 0.2),
+            outline: Color::NONE,
+            correct: Color::rgb(0.0, 1.0, 0.0),
+            incorrect: Color::rgb(1.0, 0.0, 0.0),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            clear: Color::rgb(0.93, 0.93, 0.91),
+            background: Color::rgb(0.97, 0.97, 0.95),
+            surface: Color::rgb(0.85, 0.85, 0.83),
+            text: Color::rgb(0.1, 0.1, 0.1),
+            button: ButtonColors {
+                normal: Color::rgb(0.75, 0.75, 0.73),
+                hovered: Color::rgb(0.68, 0.68, 0.66),
+                pressed: Color::rgb(0.6, 0.6, 0.58),
+                disabled: Color::rgb(0.88, 0.88, 0.86),
+            },
+            button_text: Color::rgb(0.1, 0.1, 0.1),
+            map_node: ButtonColors {
+                normal: Color::rgb(0.95, 0.6, 0.55),
+                hovered: Color::rgb(0.98, 0.7, 0.65),
+                pressed: Color::rgb(0.85, 0.45, 0.4),
+                disabled: Color::rgb(0.9, 0.82, 0.8),
+            },
+            focus: Color::rgb(0.1, 0.35, 0.9),
+            outline: Color::NONE,
+            correct: Color::rgb(0.2, 0.7, 0.2),
+            incorrect: Color::rgb(0.85, 0.15, 0.15),
+        }
+    }
+
+    /// The palette of `mode`, adjusted for `vision` and optionally raised to high contrast.
+    pub fn new(mode: ThemeMode, vision: ColorVision, high_contrast: bool) -> Self {
+        let palette = match mode {
+            ThemeMode::Dark => Self::dark(),
+            ThemeMode::Light => Self::light(),
+        };
+        let palette = palette.with_color_vision(mode, vision);
+        if high_contrast {
+            palette.with_high_contrast(mode)
+        } else {
+            palette
+        }
+    }
+
+    /// Swaps the result and map node colours for ones from the Okabe-Ito set that stay
+    /// distinct for `vision`.
+    fn with_color_vision(self, mode: ThemeMode, vision: ColorVision) -> Self {
+        let (correct, incorrect, map_node) = match (vision, mode) {
+            (ColorVision::Typical, _) => return self,
+            (ColorVision::RedGreen, ThemeMode::Dark) => (
+                Color::rgb_u8(86, 180, 233),
+                Color::rgb_u8(230, 159, 0),
+                ButtonColors {
+                    normal: Color::rgb_u8(230, 159, 0),
+                    hovered: Color::rgb_u8(240, 190, 80),
+                    pressed: Color::rgb_u8(170, 115, 0),
+                    disabled: Color::rgb_u8(100, 85, 55),
+                },
+            ),
+            (ColorVision::RedGreen, ThemeMode::Light) => (
+                Color::rgb_u8(0, 114, 178),
+                Color::rgb_u8(213, 94, 0),
+                ButtonColors {
+                    normal: Color::rgb_u8(240, 190, 80),
+                    hovered: Color::rgb_u8(245, 210, 130),
+                    pressed: Color::rgb_u8(230, 159, 0),
+                    disabled: Color::rgb_u8(235, 225, 205),
+                },
+            ),
+            (ColorVision::BlueYellow, ThemeMode::Dark) => (
+                Color::rgb_u8(0, 158, 115),
+                Color::rgb_u8(213, 94, 0),
+                ButtonColors {
+                    normal: Color::rgb_u8(213, 94, 0),
+                    hovered: Color::rgb_u8(230, 130, 60),
+                    pressed: Color::rgb_u8(160, 70, 0),
+                    disabled: Color::rgb_u8(100, 70, 50),
+                },
+            ),
+            (ColorVision::BlueYellow, ThemeMode::Light) => (
+                Color::rgb_u8(0, 130, 95),
+                Color::rgb_u8(200, 80, 0),
+                ButtonColors {
+                    normal: Color::rgb_u8(240, 150, 110),
+                    hovered: Color::rgb_u8(245, 180, 150),
+                    pressed: Color::rgb_u8(213, 94, 0),
+                    disabled: Color::rgb_u8(235, 215, 205),
+                },
+            ),
+        };
+        Self {
+            correct,
+            incorrect,
+            map_node,
+            ..self
+        }
+    }
+
+    /// Pure black and white surroundings, so text and buttons stand out as much as possible.
+    fn with_high_contrast(self, mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Dark => Self {
+                clear: Color::BLACK,
+                background: Color::BLACK,
+                surface: Color::BLACK,
+                text: Color::WHITE,
+                button: ButtonColors {
+                    normal: Color::rgb(0.3, 0.3, 0.3),
+                    hovered: Color::rgb(0.45, 0.45, 0.45),
+                    pressed: Color::rgb(0.2, 0.2, 0.2),
+                    disabled: Color::rgb(0.12, 0.12, 0.12),
+                },
+                button_text: Color::WHITE,
+                focus: Color::rgb(1.0, 1.0, 0.0),
+                outline: Color::WHITE,
+                ..self
+            },
+            ThemeMode::Light => Self {
+                clear: Color::WHITE,
+                background: Color::WHITE,
+                surface: Color::WHITE,
+                text: Color::BLACK,
+                button: ButtonColors {
+                    normal: Color::rgb(0.8, 0.8, 0.8),
+                    hovered: Color::rgb(0.7, 0.7, 0.7),
+                    pressed: Color::rgb(0.6, 0.6, 0.6),
+                    disabled: Color::rgb(0.93, 0.93, 0.93),
+                },
+                button_text: Color::BLACK,
+                focus: Color::rgb(0.0, 0.0, 0.8),
+                outline: Color::BLACK,
+                ..self
+            },
+        }
+    }
+
+    pub fn color(&self, color: ThemeColor) -> Color {
+        match color {
+            ThemeColor::Background => self.background,
+            ThemeColor::Surface => self.surface,
+            ThemeColor::Text => self.text,
+            ThemeColor::ButtonText => self.button_text,
+        }
+    }
+}
+
+/// Smallest width and height of anything that can be pressed, in logical pixels, so it can
+/// be hit with a finger.
+pub const MIN_TARGET_SIZE: f32 = 44.0;
+
+/// Text sizes offered to the player, as factors on the default font sizes.
+pub const TEXT_SCALES: [f32; 4] = [1.0, 1.25, 1.5, 2.0];
+
+/// Zoom levels of the whole UI offered to the player.
+pub const UI_SCALES: [f32; 3] = [1.0, 1.25, 1.5];
+
+/// Size a text is drawn in, looked up in [`UiTheme::font_sizes`] so it follows the text size
+/// setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontSizes {
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            small: 10.0,
+            medium: 18.0,
+            large: 20.0,
+        }
+    }
+}
+
+impl FontSizes {
+    pub fn get(&self, size: FontSize) -> f32 {
+        match size {
+            FontSize::Small => self.small,
+            FontSize::Medium => self.medium,
+            FontSize::Large => self.large,
+        }
+    }
+
+    pub fn scaled(self, factor: f32) -> Self {
+        Self {
+            small: self.small * factor,
+            medium: self.medium * factor,
+            large: self.large * factor,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spacing {
+    pub small: f32,
+    pub large: f32,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            small: 5.0,
+            large: 20.0,
+        }
+    }
+}
+
+/// Fonts, colours and sizes shared by every screen, loaded once at startup.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct UiTheme {
+    pub mode: ThemeMode,
+    pub font: Handle<Font>,
+    /// Font of [`SymbolText`].
+    pub symbol_font: Handle<Font>,
+    pub palette: Palette,
+    pub font_sizes: FontSizes,
+    pub spacing: Spacing,
+}
+
+impl UiTheme {
+    pub fn new(settings: &Settings, font: Handle<Font>, symbol_font: Handle<Font>) -> Self {
+        Self {
+            mode: settings.theme,
+            font,
+            symbol_font,
+            palette: Palette::new(
+                settings.theme,
+                settings.color_vision,
+                settings.high_contrast,
+            ),
+            font_sizes: FontSizes::default().scaled(settings.text_scale),
+            spacing: Spacing::default(),
+        }
+    }
+
+    pub fn text_style(&self, font_size: FontSize) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: self.font_sizes.get(font_size),
+            color: self.palette.text,
+        }
+    }
+
+    /// Default layout of buttons: centred content, themed margin and padding, room for the
+    /// focus border, and at least [`MIN_TARGET_SIZE`] in each direction.
+    pub fn button_style(&self) -> Style {
+        let spacing = Val::Px(self.spacing.small);
+        Style {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            column_gap: spacing,
+            margin: UiRect::all(spacing),
+            padding: UiRect::all(spacing),
+            border: UiRect::all(Val::Px(2.0)),
+            min_width: Val::Px(MIN_TARGET_SIZE),
+            min_height: Val::Px(MIN_TARGET_SIZE),
+            ..default()
+        }
+    }
+
+    /// Style of [`SymbolText`], in the colour of regular text.
+    pub fn symbol_style(&self, font_size: FontSize) -> TextStyle {
+        TextStyle {
+            font: self.symbol_font.clone(),
+            ..self.text_style(font_size)
+        }
+    }
+
+    pub fn button_text_style(&self, font_size: FontSize) -> TextStyle {
+        TextStyle {
+            color: self.palette.button_text,
+            ..self.text_style(font_size)
+        }
+    }
+}
+
+impl FromWorld for UiTheme {
+    fn from_world(world: &mut World) -> Self {
+        let settings = world
+            .get_resource::<Settings>()
+            .cloned()
+            .unwrap_or_default();
+        let fonts = world.resource::<Fonts>();
+        let font = fonts.for_language(&settings.language, world.resource::<AssetServer>());
+        Self::new(&settings, font, fonts.symbols())
+    }
+}
+
+/// Keeps the background colour of a node in sync with the active theme.
+#[derive(Component)]
+pub struct ThemedBackground(pub ThemeColor);
+
+/// Keeps the colour, font and size of all sections of a text in sync with the active theme.
+#[derive(Component)]
+pub struct ThemedText(pub ThemeColor, pub FontSize);
+
+/// Marks a [`ThemedText`] drawn in the symbol font rather than the font of the language.
+#[derive(Component)]
+pub struct SymbolText;
+
+pub fn theme_plugin(app: &mut App) {
+    app.init_resource::<Fonts>()
+        .init_resource::<UiTheme>()
+        .add_systems(Startup, preload_fonts)
+        .add_systems(Update, apply_ui_scale.run_if(resource_changed::<Settings>))
+        .add_systems(
+            Update,
+            (
+                // Also when a font fails to load, so it is replaced by the next in its stack.
+                switch_theme.run_if(
+                    resource_changed::<Settings>.or_else(on_event::<AssetLoadFailedEvent<Font>>()),
+                ),
+                (apply_clear_color, apply_theme).run_if(resource_changed::<UiTheme>),
+            )
+                .chain(),
+        );
+}
+
+fn preload_fonts(fonts: Res<Fonts>, mut preload: ResMut<Preload>) {
+    for (_, font) in &fonts.0 {
+        preload.add(font.clone());
+    }
+}
+
+/// `UiScale` only exists with the UI plugin, so it is inserted rather than updated.
+fn apply_ui_scale(mut commands: Commands, settings: Res<Settings>, scale: Option<Res<UiScale>>) {
+    if scale.is_none_or(|scale| scale.0 != settings.ui_scale) {
+        commands.insert_resource(UiScale(settings.ui_scale));
+    }
+}
+
+fn switch_theme(
+    settings: Res<Settings>,
+    fonts: Res<Fonts>,
+    asset_server: Res<AssetServer>,
+    mut theme: ResMut<UiTheme>,
+) {
+    let font = fonts.for_language(&settings.language, &asset_server);
+    let updated = UiTheme::new(&settings, font, fonts.symbols());
+    if *theme != updated {
+        *theme = updated;
+    }
+}
+
+fn apply_clear_color(mut commands: Commands, theme: Res<UiTheme>) {
+    commands.insert_resource(ClearColor(theme.palette.clear));
+}
+
+fn apply_theme(
+    theme: Res<UiTheme>,
+    mut backgrounds: Query<(&ThemedBackground, &mut BackgroundColor)>,
+    mut texts: Query<(&ThemedText, &mut Text, Has<SymbolText>)>,
+) {
+    for (themed, mut background) in backgrounds.iter_mut() {
+        *background = theme.palette.color(themed.0).into();
+    }
+    for (themed, mut text, symbol) in texts.iter_mut() {
+        let font = if symbol {
+            &theme.symbol_font
+        } else {
+            &theme.font
+        };
+        for section in text.sections.iter_mut() {
+            section.style.color = theme.palette.color(themed.0);
+            section.style.font = font.clone();
+            section.style.font_size = theme.font_sizes.get(themed.1);
+        }
+    }
+}
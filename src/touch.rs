@@ -0,0 +1,171 @@
+use bevy::{
+    input::{
+        touch::{Touch, TouchInput, Touches},
+        InputSystem,
+    },
+    prelude::*,
+    utils::HashMap,
+    window::{CursorMoved, RequestRedraw},
+};
+
+/// Furthest a touch may travel, in logical pixels, and still count as a tap or long press.
+const TAP_SLOP: f32 = 10.0;
+
+/// Minimum travel of a touch, in logical pixels, to count as a swipe.
+pub const SWIPE_DISTANCE: f32 = 50.0;
+
+/// How long a touch is held in place before it counts as a long press.
+const LONG_PRESS_SECONDS: f32 = 0.5;
+
+/// Gesture made on a touch screen, in logical window coordinates.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// A short touch that stayed in place, sent when it is released.
+    Tap(Vec2),
+    /// A touch held in place, sent once while it is still down.
+    LongPress(Vec2),
+    /// Travel of a single touch since the last frame, once it moved beyond a tap.
+    Pan(Vec2),
+    /// Total travel of a single touch that moved far enough, sent when it is released.
+    Swipe(Vec2),
+    /// Two touches moving apart or together; `scale` is the change in their distance since
+    /// the last frame.
+    Pinch { center: Vec2, scale: f32 },
+}
+
+/// Device the player pointed with last. Touch screens have no hover, so hover feedback and
+/// hover-only details are left out while it is [`Pointer::Touch`].
+#[derive(Resource, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum Pointer {
+    #[default]
+    Mouse,
+    Touch,
+}
+
+/// Sets the gesture events apart from the input they are recognised from.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecognizeGestures;
+
+#[derive(Default)]
+struct TrackedTouch {
+    started: f32,
+    /// Whether the touch already became a long press, pan or pinch, so it is no tap.
+    handled: bool,
+    /// Whether the touch was part of a pinch, so it is no swipe either.
+    pinched: bool,
+}
+
+pub fn touch_plugin(app: &mut App) {
+    app.add_event::<Gesture>()
+        .init_resource::<Pointer>()
+        .add_systems(
+            PreUpdate,
+            (detect_pointer, recognize_gestures)
+                .in_set(RecognizeGestures)
+                .after(InputSystem),
+        );
+}
+
+/// Browsers also move the cursor for touches, so a cursor move only counts as the mouse in
+/// frames without touch input.
+fn detect_pointer(
+    mut touches: EventReader<TouchInput>,
+    mut cursor: EventReader<CursorMoved>,
+    mut pointer: ResMut<Pointer>,
+) {
+    let touched = touches.read().count() > 0;
+    let moved = cursor.read().count() > 0;
+    let latest = match (touched, moved) {
+        (true, _) => Pointer::Touch,
+        (false, true) => Pointer::Mouse,
+        (false, false) => return,
+    };
+    if *pointer != latest {
+        *pointer = latest;
+    }
+}
+
+/// Held touches ordered by id, so the same two make up a pinch every frame.
+fn held_touches(touches: &Touches) -> Vec<&Touch> {
+    let mut held: Vec<&Touch> = touches.iter().collect();
+    held.sort_by_key(|touch| touch.id());
+    held
+}
+
+fn recognize_gestures(
+    time: Res<Time>,
+    touches: Res<Touches>,
+    mut tracked: Local<HashMap<u64, TrackedTouch>>,
+    mut pinch_distance: Local<Option<f32>>,
+    mut gestures: EventWriter<Gesture>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    let now = time.elapsed_seconds();
+    for touch in touches.iter_just_pressed() {
+        tracked.insert(
+            touch.id(),
+            TrackedTouch {
+                started: now,
+                ..default()
+            },
+        );
+    }
+
+    let held = held_touches(&touches);
+    if let [first, second, ..] = held[..] {
+        for touch in &held {
+            let state = tracked.entry(touch.id()).or_default();
+            state.handled = true;
+            state.pinched = true;
+        }
+        let distance = first.position().distance(second.position());
+        if let Some(previous) = pinch_distance.filter(|previous| *previous > 0.0) {
+            if distance != previous {
+                gestures.send(Gesture::Pinch {
+                    center: (first.position() + second.position()) / 2.0,
+                    scale: distance / previous,
+                });
+            }
+        }
+        *pinch_distance = Some(distance);
+    } else {
+        *pinch_distance = None;
+        if let [touch] = held[..] {
+            let state = tracked.entry(touch.id()).or_default();
+            if touch.distance().length() > TAP_SLOP {
+                state.handled = true;
+                if touch.delta() != Vec2::ZERO {
+                    gestures.send(Gesture::Pan(touch.delta()));
+                }
+            } else if !state.handled && now - state.started >= LONG_PRESS_SECONDS {
+                state.handled = true;
+                gestures.send(Gesture::LongPress(touch.position()));
+            }
+        }
+    }
+    // A touch held still sends no input, so keep updating until it becomes a long press.
+    if held
+        .iter()
+        .any(|touch| tracked.get(&touch.id()).is_some_and(|state| !state.handled))
+    {
+        redraw.send(RequestRedraw);
+    }
+
+    for touch in touches.iter_just_released() {
+        let Some(state) = tracked.remove(&touch.id()) else {
+            continue;
+        };
+        let travel = touch.distance();
+        if state.pinched {
+            continue;
+        }
+        if travel.length() >= SWIPE_DISTANCE {
+            gestures.send(Gesture::Swipe(travel));
+        } else if !state.handled && travel.length() <= TAP_SLOP {
+            gestures.send(Gesture::Tap(touch.position()));
+        }
+    }
+    for touch in touches.iter_just_canceled() {
+        tracked.remove(&touch.id());
+    }
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use bevy::{
+    a11y::{
+        accesskit::{Live, Role},
+        AccessibilityNode,
+    },
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState,
+    },
+    prelude::*,
+};
+use common::*;
+use konnektoren_game::{
+    app_state::AppState,
+    button::{Disabled, Focused},
+    game::OptionNode,
+    map::ChallengeNode,
+    slides::SlideAction,
+};
+
+/// Role and name of the accessibility node of every entity carrying `C`.
+fn described<C: Component>(app: &mut App) -> Vec<(Role, String)> {
+    app.world
+        .query_filtered::<&AccessibilityNode, With<C>>()
+        .iter(&app.world)
+        .map(|node| (node.role(), node.name().unwrap_or_default().to_string()))
+        .collect()
+}
+
+/// Names of the nodes whose changes are read out.
+fn live_regions(app: &mut App) -> Vec<String> {
+    app.world
+        .query::<&AccessibilityNode>()
+        .iter(&app.world)
+        .filter(|node| node.live() == Some(Live::Polite))
+        .map(|node| node.name().unwrap_or_default().to_string())
+        .collect()
+}
+
+fn focused_action(app: &mut App) -> Option<SlideAction> {
+    app.world
+        .query_filtered::<&SlideAction, With<Focused>>()
+        .iter(&app.world)
+        .next()
+        .copied()
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world.send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+#[test]
+fn buttons_are_named_after_their_labels() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    advance(&mut app, 1);
+    let mut slide_buttons = described::<SlideAction>(&mut app);
+    slide_buttons.sort_by(|a, b| a.1.cmp(&b.1));
+    assert_eq!(
+        slide_buttons,
+        [
+            (Role::Button, "Next".to_string()),
+            (Role::Button, "Previous".to_string()),
+            (Role::Button, "Skip".to_string()),
+        ]
+    );
+
+    tap_key(&mut app, KeyCode::Escape);
+    advance_until_state(&mut app, AppState::Map, 20);
+    advance(&mut app, 1);
+    let challenge = game_state(&app).game.game_path.challenges[0].name.clone();
+    assert!(described::<ChallengeNode>(&mut app).contains(&(Role::Button, challenge)));
+
+    let node = app
+        .world
+        .query_filtered::<Entity, With<ChallengeNode>>()
+        .iter(&app.world)
+        .next()
+        .unwrap();
+    press_entity(&mut app, node);
+    advance_until_state(&mut app, AppState::Game, 20);
+    advance(&mut app, 1);
+    let options = described::<OptionNode>(&mut app);
+    assert!(!options.is_empty());
+    assert!(options
+        .iter()
+        .all(|(role, name)| *role == Role::Button && name.starts_with(char::is_numeric)));
+
+    let option = app
+        .world
+        .query_filtered::<Entity, With<OptionNode>>()
+        .iter(&app.world)
+        .next()
+        .unwrap();
+    app.world.entity_mut(option).insert(Disabled);
+    advance(&mut app, 1);
+    assert!(app
+        .world
+        .get::<AccessibilityNode>(option)
+        .unwrap()
+        .is_disabled());
+    app.world.entity_mut(option).remove::<Disabled>();
+    advance(&mut app, 1);
+    assert!(!app
+        .world
+        .get::<AccessibilityNode>(option)
+        .unwrap()
+        .is_disabled());
+}
+
+#[test]
+fn question_and_answer_feedback_are_read_out() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    tap_key(&mut app, KeyCode::Escape);
+    advance_until_state(&mut app, AppState::Map, 20);
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    press::<ChallengeNode>(&mut app, |node| node.0 .1 == id);
+    advance_until_state(&mut app, AppState::Game, 20);
+    advance(&mut app, 1);
+
+    assert!(live_regions(&mut app)
+        .iter()
+        .any(|name| name.starts_with("Question: ")));
+
+    press::<OptionNode>(&mut app, |_| true);
+    assert!(live_regions(&mut app)
+        .iter()
+        .any(|name| name.starts_with("Correct: ") || name.starts_with("Incorrect: ")));
+}
+
+#[test]
+fn tab_moves_focus_in_reading_order_and_enter_presses() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert_eq!(focused_action(&mut app), None);
+
+    tap_key(&mut app, KeyCode::Tab);
+    assert_eq!(focused_action(&mut app), Some(SlideAction::Previous));
+    tap_key(&mut app, KeyCode::Tab);
+    assert_eq!(focused_action(&mut app), Some(SlideAction::Next));
+
+    send_key(&mut app, KeyCode::ShiftLeft, ButtonState::Pressed);
+    tap_key(&mut app, KeyCode::Tab);
+    tap_key(&mut app, KeyCode::Tab);
+    send_key(&mut app, KeyCode::ShiftLeft, ButtonState::Released);
+    assert_eq!(focused_action(&mut app), Some(SlideAction::Skip));
+
+    tap_key(&mut app, KeyCode::Enter);
+    advance_until_state(&mut app, AppState::Map, 20);
+}
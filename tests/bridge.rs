@@ -0,0 +1,54 @@
+mod common;
+
+use common::*;
+use konnektoren_game::{
+    app_state::AppState,
+    bridge::{HostCommand, HostEvent},
+    game::OptionNode,
+    settings::Settings,
+};
+
+#[test]
+fn host_page_controls_the_game_and_hears_about_progress() {
+    let mut app = test_app();
+    app.world.send_event(HostCommand::SetLanguage {
+        language: "de".to_string(),
+    });
+    advance(&mut app, 1);
+    assert_eq!(app.world.resource::<Settings>().language, "de");
+
+    let mut content = game_state(&app).game.game_path.clone();
+    content.id = "loaded".to_string();
+    content.challenges.truncate(1);
+    let id = content.challenges[0].id.clone();
+    app.world.send_event(HostCommand::LoadContent { content });
+    app.world.send_event(HostCommand::StartChallenge {
+        challenge: id.clone(),
+    });
+    advance(&mut app, 2);
+    assert_eq!(game_state(&app).game.game_path.id, "loaded");
+    assert!(
+        events::<HostEvent>(&app).contains(&HostEvent::ChallengeStarted {
+            challenge_id: id.clone()
+        })
+    );
+    advance_until_state(&mut app, AppState::Game, 20);
+
+    while !game_state(&app).is_completed() {
+        press::<OptionNode>(&mut app, |option| option.index == 0);
+    }
+    let completed = events::<HostEvent>(&app)
+        .into_iter()
+        .find_map(|event| match event {
+            HostEvent::ChallengeCompleted {
+                challenge_id,
+                score,
+                outcomes,
+            } => Some((challenge_id, score, outcomes)),
+            _ => None,
+        })
+        .expect("completion reported to the host page");
+    assert_eq!(completed.0, id);
+    assert_eq!(completed.1, game_state(&app).score());
+    assert_eq!(completed.2.len(), game_state(&app).task_count());
+}
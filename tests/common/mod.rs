@@ -0,0 +1,207 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState, InputPlugin,
+    },
+    prelude::*,
+    text::FontLoader,
+    time::TimeUpdateStrategy,
+    window::{ExitCondition, WindowPlugin},
+};
+use konnektoren_game::{
+    app_state::AppState, game_state::GameState, plugin::KonnektorenPlugin, preload::Preload,
+    profile::ProfileStore,
+};
+
+/// Simulated duration of a single frame.
+pub const FRAME: Duration = Duration::from_millis(50);
+
+/// Builds the game without a window or GPU, advancing time by [`FRAME`] per update.
+pub fn test_app() -> App {
+    test_app_with(KonnektorenPlugin::new())
+}
+
+/// Builds the parts of the game `plugin` includes, like [`test_app`], keeping progress in
+/// memory.
+pub fn test_app_with(plugin: KonnektorenPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        InputPlugin,
+        WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+    ))
+    .add_plugins(ImagePlugin::default())
+    .init_asset::<Font>()
+    .init_asset_loader::<FontLoader>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    .add_plugins(plugin.with_profile_store(ProfileStore::Memory));
+    app.finish();
+    app.cleanup();
+    app.update();
+    wait_for_assets(&mut app);
+    app
+}
+
+/// Runs frames without advancing game time until all preloaded assets are loaded, so tests
+/// do not depend on how fast the machine decodes them.
+pub fn wait_for_assets(app: &mut App) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    let mut settled_frames = 0;
+    for _ in 0..2000 {
+        app.update();
+        let progress = app
+            .world
+            .resource::<Preload>()
+            .progress(app.world.resource::<AssetServer>());
+        assert!(
+            progress.failed.is_empty(),
+            "failed to load {:?}",
+            progress.failed
+        );
+        // Loaded assets can queue more, e.g. a manifest its images, so wait a few frames.
+        settled_frames = if progress.is_complete() {
+            settled_frames + 1
+        } else {
+            0
+        };
+        if settled_frames == 3 {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    panic!("assets not loaded in time");
+}
+
+pub fn advance(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+pub fn advance_by(app: &mut App, duration: Duration) {
+    let frames = duration.as_millis().div_ceil(FRAME.as_millis()) as usize;
+    advance(app, frames);
+}
+
+pub fn app_state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+pub fn game_state(app: &App) -> &GameState {
+    app.world.resource::<GameState>()
+}
+
+/// Runs frames until the app is in `state`, panicking after `max_frames`.
+pub fn advance_until_state(app: &mut App, state: AppState, max_frames: usize) {
+    for _ in 0..max_frames {
+        if app_state(app) == state {
+            return;
+        }
+        app.update();
+    }
+    assert_eq!(
+        app_state(app),
+        state,
+        "state not reached after {max_frames} frames"
+    );
+}
+
+/// Presses the first button carrying a `C` that matches `filter`, then runs two frames
+/// so the press is handled and any resulting state transition is applied.
+pub fn press<C: Component>(app: &mut App, filter: impl Fn(&C) -> bool) {
+    let entity = app
+        .world
+        .query_filtered::<(Entity, &C), With<Button>>()
+        .iter(&app.world)
+        .find(|(_, component)| filter(component))
+        .map(|(entity, _)| entity)
+        .unwrap_or_else(|| panic!("no button with {}", std::any::type_name::<C>()));
+    press_entity(app, entity);
+}
+
+/// Presses the first button whose label text equals `label`.
+pub fn press_label(app: &mut App, label: &str) {
+    let entity = app
+        .world
+        .query_filtered::<(Entity, &Children), With<Button>>()
+        .iter(&app.world)
+        .find(|(_, children)| {
+            children.iter().any(|child| {
+                app.world
+                    .get::<Text>(*child)
+                    .is_some_and(|text| text.sections.iter().any(|s| s.value == label))
+            })
+        })
+        .map(|(entity, _)| entity)
+        .unwrap_or_else(|| panic!("no button labelled {label:?}"));
+    press_entity(app, entity);
+}
+
+pub fn press_entity(app: &mut App, entity: Entity) {
+    *app.world.get_mut::<Interaction>(entity).unwrap() = Interaction::Pressed;
+    app.update();
+    if let Some(mut interaction) = app.world.get_mut::<Interaction>(entity) {
+        *interaction = Interaction::None;
+    }
+    app.update();
+}
+
+/// Taps `key_code` on the keyboard, running one frame while it is held and one after release.
+pub fn tap_key(app: &mut App, key_code: KeyCode) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world.send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+    app.update();
+}
+
+/// Returns the events of type `E` that are still buffered.
+pub fn events<E: Event + Clone>(app: &App) -> Vec<E> {
+    let events = app.world.resource::<Events<E>>();
+    events.get_reader().read(events).cloned().collect()
+}
+
+/// Collects the text of every entity carrying `C`.
+pub fn texts_with<C: Component>(app: &mut App) -> Vec<String> {
+    app.world
+        .query_filtered::<&Text, With<C>>()
+        .iter(&app.world)
+        .map(|text| {
+            text.sections
+                .iter()
+                .map(|section| section.value.as_str())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Collects the text of every entity in the world that is not hidden, e.g. leaving out
+/// the announcements for screen readers.
+pub fn all_texts(app: &mut App) -> Vec<String> {
+    app.world
+        .query_filtered::<(&Text, &Visibility), With<Node>>()
+        .iter(&app.world)
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map(|(text, _)| {
+            text.sections
+                .iter()
+                .map(|section| section.value.as_str())
+                .collect::<String>()
+        })
+        .collect()
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey},
+        ButtonState,
+    },
+    prelude::*,
+    window::ReceivedCharacter,
+};
+use common::*;
+use konnektoren_game::{
+    app_state::AppState, button::Focused, debug::DebugConsole, profile::Profile,
+    slides::SlideAction,
+};
+
+/// Types `line` into the console and submits it.
+fn type_command(app: &mut App, line: &str) {
+    for c in line.chars() {
+        app.world.send_event(ReceivedCharacter {
+            window: Entity::PLACEHOLDER,
+            char: c.to_string().into(),
+        });
+    }
+    app.update();
+    tap_key(app, KeyCode::Enter);
+}
+
+fn console(app: &App) -> &DebugConsole {
+    app.world.resource::<DebugConsole>()
+}
+
+#[test]
+fn console_drives_the_game_while_the_overlay_shows_its_state() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    tap_key(&mut app, KeyCode::F12);
+    assert!(console(&app).open);
+    // Keys go to the console, not to the slides.
+    tap_key(&mut app, KeyCode::Escape);
+    advance(&mut app, 10);
+    assert_eq!(app_state(&app), AppState::Slides);
+
+    type_command(&mut app, "fly away");
+    assert_eq!(console(&app).output, "Unknown command: fly away");
+
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    type_command(&mut app, &format!("start challenge {}", id));
+    advance_until_state(&mut app, AppState::Game, 20);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text.starts_with("State: Game\nChallenge: ") && text.contains(&id)));
+
+    type_command(&mut app, "answer correctly");
+    assert_eq!(game_state(&app).answers(), [true]);
+    type_command(&mut app, "complete challenge");
+    assert!(game_state(&app).is_completed());
+    assert_eq!(game_state(&app).score(), 100.0);
+
+    app.world.resource_mut::<Profile>().intro_completed = true;
+    type_command(&mut app, "reset progress");
+    assert_eq!(*app.world.resource::<Profile>(), Profile::default());
+
+    type_command(&mut app, "goto map");
+    advance_until_state(&mut app, AppState::Map, 20);
+
+    tap_key(&mut app, KeyCode::Backquote);
+    assert!(!console(&app).open);
+}
+
+#[test]
+fn submitting_a_command_does_not_press_the_focused_button() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    let button = app
+        .world
+        .query_filtered::<Entity, With<SlideAction>>()
+        .iter(&app.world)
+        .next()
+        .unwrap();
+    app.world.entity_mut(button).insert(Focused);
+
+    tap_key(&mut app, KeyCode::F12);
+    for c in "help".chars() {
+        app.world.send_event(ReceivedCharacter {
+            window: Entity::PLACEHOLDER,
+            char: c.to_string().into(),
+        });
+    }
+    app.update();
+    // Checked in the frame Enter goes down, as a pressed button is released the next.
+    app.world.send_event(KeyboardInput {
+        key_code: KeyCode::Enter,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    assert_eq!(console(&app).output.split(':').next(), Some("Commands"));
+    assert_ne!(
+        *app.world.get::<Interaction>(button).unwrap(),
+        Interaction::Pressed
+    );
+    assert_eq!(app_state(&app), AppState::Slides);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use konnektoren_game::{
+    app_state::AppState,
+    error::ErrorReport,
+    game::events::{GameAction, TaskAdvanced},
+    game_state::GameState,
+    plugin::{KonnektorenPlugin, Screens},
+    screen::TransitionTo,
+};
+
+fn map_app() -> App {
+    test_app_with(KonnektorenPlugin::new().with_screens(Screens {
+        splash: false,
+        ..Screens::ALL
+    }))
+}
+
+fn details_shown(app: &mut App, details: &str) -> bool {
+    app.world
+        .query::<(&Text, &Style)>()
+        .iter(&app.world)
+        .any(|(text, style)| {
+            style.display != Display::None
+                && text.sections.iter().any(|s| s.value.contains(details))
+        })
+}
+
+#[test]
+fn failed_challenge_shows_error_screen_with_details() {
+    let mut app = map_app();
+    assert_eq!(app_state(&app), AppState::Map);
+
+    app.world
+        .send_event(GameAction::StartChallenge("no-such-challenge".to_string()));
+    advance_until_state(&mut app, AppState::Error, 20);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "This challenge could not be started."));
+    assert!(!details_shown(&mut app, "no-such-challenge"));
+
+    press_label(&mut app, "Show details");
+    assert!(details_shown(&mut app, "no-such-challenge"));
+    press_label(&mut app, "Hide details");
+    assert!(!details_shown(&mut app, "no-such-challenge"));
+
+    press_label(&mut app, "Return to map");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert_eq!(app.world.resource::<ErrorReport>().current(), None);
+}
+
+#[test]
+fn later_errors_keep_the_first_one_on_the_error_screen() {
+    let mut app = map_app();
+    app.world
+        .send_event(GameAction::StartChallenge("no-such-challenge".to_string()));
+    advance_until_state(&mut app, AppState::Error, 20);
+    advance(&mut app, 2);
+
+    app.world
+        .send_event(GameAction::StartChallenge("another-challenge".to_string()));
+    advance(&mut app, 2);
+    assert!(events::<TransitionTo>(&app).is_empty());
+    let error = app.world.resource::<ErrorReport>().current().unwrap();
+    assert!(error.details.contains("no-such-challenge"));
+}
+
+#[test]
+fn challenge_player_shows_error_without_a_way_to_the_missing_map() {
+    let mut app = test_app_with(KonnektorenPlugin::challenge_player("no-such-challenge"));
+    advance_until_state(&mut app, AppState::Error, 20);
+    // Without the splash screen, nothing waits for the texts to load.
+    advance(&mut app, 10);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "This challenge could not be started."));
+    assert_eq!(
+        app.world
+            .resource::<ErrorReport>()
+            .current()
+            .unwrap()
+            .message,
+        "error-challenge"
+    );
+    assert!(!all_texts(&mut app)
+        .iter()
+        .any(|text| text == "Return to map"));
+}
+
+#[test]
+fn missing_question_is_reported_instead_of_panicking() {
+    let mut app = map_app();
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    app.world.send_event(GameAction::StartChallenge(id));
+    advance_until_state(&mut app, AppState::Game, 20);
+
+    app.world.resource_mut::<GameState>().current_task_index = 99;
+    app.world.send_event(TaskAdvanced { task_index: 99 });
+    advance_until_state(&mut app, AppState::Error, 20);
+    let error = app.world.resource::<ErrorReport>().current().unwrap();
+    assert_eq!(error.message, "error-content");
+    assert!(error.details.contains("no question 100"));
+}
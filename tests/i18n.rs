@@ -0,0 +1,88 @@
+use konnektoren_game::{
+    i18n::{fallback_chain, is_right_to_left, Localization, LocalizedText},
+    theme::font_stack,
+};
+
+fn locale_source(language: &str) -> Option<&'static str> {
+    match language {
+        "en" => Some(include_str!("../assets/locales/en/main.ftl")),
+        "de" => Some(include_str!("../assets/locales/de/main.ftl")),
+        "uk" => Some(include_str!("../assets/locales/uk/main.ftl")),
+        _ => None,
+    }
+}
+
+fn localization(language: &str) -> Localization {
+    Localization::new(language, |id| locale_source(&id.to_string()))
+}
+
+#[test]
+fn regional_languages_fall_back_to_their_base_language() {
+    let chain: Vec<String> = fallback_chain("de-AT")
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+    assert_eq!(chain, ["de-AT", "de", "en"]);
+
+    assert_eq!(localization("de-AT").text("slides-skip"), "Überspringen");
+    assert_eq!(localization("fr").text("slides-skip"), "Skip");
+}
+
+#[test]
+fn missing_messages_fall_back_to_english_then_the_id() {
+    let localization = Localization::new("de", |id| match id.to_string().as_str() {
+        "de" => Some("slides-skip = Überspringen"),
+        "en" => locale_source("en"),
+        _ => None,
+    });
+    assert_eq!(localization.text("slides-skip"), "Überspringen");
+    assert_eq!(localization.text("slides-next"), "Next");
+    assert_eq!(localization.text("no-such-message"), "no-such-message");
+}
+
+#[test]
+fn messages_are_pluralised_per_language() {
+    let error = |language: &str, files: &[&str]| {
+        localization(language).format(
+            &LocalizedText::new("splash-load-error")
+                .with_arg("count", files.len())
+                .with_arg("files", files.join(", ")),
+        )
+    };
+    assert_eq!(error("en", &["a.png"]), "Could not load a.png");
+    assert_eq!(
+        error("en", &["a.png", "b.png"]),
+        "Could not load 2 files: a.png, b.png"
+    );
+    assert_eq!(
+        error("de", &["a.png", "b.png"]),
+        "2 Dateien konnten nicht geladen werden: a.png, b.png"
+    );
+}
+
+#[test]
+fn multiline_messages_keep_blank_lines() {
+    let question = localization("en").format(
+        &LocalizedText::new("game-question")
+            .with_arg("question", "Wer?")
+            .with_arg("help", "Who?"),
+    );
+    assert_eq!(question, "Question: Wer?\n\nWho?");
+}
+
+#[test]
+fn right_to_left_is_decided_by_the_base_language() {
+    assert!(is_right_to_left("ar"));
+    assert!(is_right_to_left("fa-IR"));
+    assert!(!is_right_to_left("de-AT"));
+    assert!(!is_right_to_left("uk"));
+}
+
+#[test]
+fn scripts_get_a_font_stack_that_covers_them() {
+    assert_eq!(
+        font_stack("uk"),
+        ["fonts/FiraSans-Bold.ttf", "fonts/DejaVuSans-Bold.ttf"]
+    );
+    assert_eq!(font_stack("fa-IR"), ["fonts/DejaVuSans-Bold.ttf"]);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use konnektoren_game::{
+    app_state::AppState,
+    deep_link::{CurrentRoute, FollowRoute, Route},
+    launch::LaunchOptions,
+};
+
+fn current_route(app: &App) -> Option<Route> {
+    app.world.resource::<CurrentRoute>().0.clone()
+}
+
+#[test]
+fn skip_intro_goes_straight_to_the_map() {
+    let mut app = test_app();
+    app.insert_resource(LaunchOptions {
+        skip_intro: true,
+        ..Default::default()
+    });
+    advance_until_state(&mut app, AppState::Map, 100);
+}
+
+#[test]
+fn challenge_starts_right_after_loading() {
+    let mut app = test_app();
+    let id = game_state(&app).game.game_path.challenges[1].id.clone();
+    app.insert_resource(LaunchOptions {
+        challenge: Some(id.clone()),
+        ..Default::default()
+    });
+    advance_until_state(&mut app, AppState::Game, 100);
+    assert_eq!(game_state(&app).challenge.challenge_config.id, id);
+}
+
+#[test]
+fn unknown_challenge_falls_back_to_the_map() {
+    let mut app = test_app();
+    app.insert_resource(LaunchOptions {
+        challenge: Some("no-such-challenge".to_string()),
+        ..Default::default()
+    });
+    advance_until_state(&mut app, AppState::Map, 100);
+}
+
+#[test]
+fn routes_follow_the_screen_and_the_browser_history() {
+    let mut app = test_app();
+    assert_eq!(current_route(&app), None);
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    app.insert_resource(LaunchOptions {
+        challenge: Some(id.clone()),
+        ..Default::default()
+    });
+    advance_until_state(&mut app, AppState::Game, 100);
+    advance(&mut app, 1);
+    assert_eq!(current_route(&app), Some(Route::Challenge(id.clone())));
+
+    // The browser going back to the map, and forward to the challenge again.
+    app.world.send_event(FollowRoute(Route::Map));
+    advance_until_state(&mut app, AppState::Map, 20);
+    advance(&mut app, 1);
+    assert_eq!(current_route(&app), Some(Route::Map));
+
+    app.world
+        .send_event(FollowRoute(Route::Challenge(id.clone())));
+    advance_until_state(&mut app, AppState::Game, 20);
+    assert_eq!(game_state(&app).challenge.challenge_config.id, id);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use common::*;
+use konnektoren_core::game::Game;
+use konnektoren_game::{
+    app_state::AppState,
+    game::OptionNode,
+    map::ChallengeNode,
+    plugin::{KonnektorenPlugin, Screens},
+    settings::Settings,
+    theme::ThemeMode,
+};
+
+fn count<C: Component>(app: &mut App) -> usize {
+    app.world
+        .query_filtered::<(), With<C>>()
+        .iter(&app.world)
+        .count()
+}
+
+#[test]
+fn challenge_player_plays_only_the_challenge() {
+    let id = Game::default().game_path.challenges[1].id.clone();
+    let scores = Arc::new(Mutex::new(Vec::new()));
+    let recorded = scores.clone();
+    let mut app = test_app_with(
+        KonnektorenPlugin::challenge_player(id.clone()).on_challenge_completed(move |completed| {
+            recorded.lock().unwrap().push(completed.score)
+        }),
+    );
+    assert_eq!(app_state(&app), AppState::Game);
+    assert_eq!(game_state(&app).challenge.challenge_config.id, id);
+    assert_eq!(count::<ChallengeNode>(&mut app), 0);
+
+    while !game_state(&app).is_completed() {
+        press::<OptionNode>(&mut app, |option| option.index == 0);
+    }
+    advance(&mut app, 1);
+    assert_eq!(*scores.lock().unwrap(), [game_state(&app).score()]);
+    assert_eq!(app_state(&app), AppState::Game);
+}
+
+#[test]
+fn screens_and_settings_can_be_chosen() {
+    let mut app = test_app_with(
+        KonnektorenPlugin::new()
+            .with_screens(Screens {
+                splash: false,
+                ..Screens::ALL
+            })
+            .with_theme(ThemeMode::Light),
+    );
+    assert_eq!(app_state(&app), AppState::Map);
+    assert!(count::<ChallengeNode>(&mut app) > 0);
+    assert_eq!(app.world.resource::<Settings>().theme, ThemeMode::Light);
+
+    let app = test_app_with(KonnektorenPlugin::new().with_initial_state(AppState::Slides));
+    assert_eq!(app_state(&app), AppState::Slides);
+}
@@ -0,0 +1,18 @@
+use konnektoren_game::profile::{Profile, ProfileStore};
+
+#[test]
+fn profile_round_trips_through_file() {
+    let dir = std::env::temp_dir().join(format!("konnektoren-profile-{}", std::process::id()));
+    let store = ProfileStore::File(dir.join("profile.json"));
+
+    assert_eq!(store.load().unwrap(), Profile::default());
+
+    let profile = Profile {
+        intro_completed: true,
+        reduced_motion: Some(true),
+    };
+    store.save(&profile).unwrap();
+    assert_eq!(store.load().unwrap(), profile);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
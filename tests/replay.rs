@@ -0,0 +1,120 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::{prelude::*, window::RequestRedraw};
+use common::*;
+use konnektoren_core::game::Game;
+use konnektoren_game::{
+    app_state::AppState,
+    game::{events::GameAction, OptionNode},
+    launch::LaunchOptions,
+    map::ChallengeNode,
+    plugin::KonnektorenPlugin,
+    random::GameRng,
+    replay::{Replay, ReplayEntry, ReplayPlayback, ReplayRecorder},
+};
+
+/// Plays the first challenge, answering each task with the first option, and returns the
+/// recorded replay.
+fn play_first_challenge(app: &mut App) -> Replay {
+    advance_until_state(app, AppState::Slides, 100);
+    press_label(app, "Skip");
+    advance_until_state(app, AppState::Map, 20);
+    let id = game_state(app).game.game_path.challenges[0].id.clone();
+    press::<ChallengeNode>(app, |node| node.0 .1 == id);
+    advance_until_state(app, AppState::Game, 20);
+    while !game_state(app).is_completed() {
+        press::<OptionNode>(app, |option| option.index == 0);
+    }
+    app.world.resource::<ReplayRecorder>().replay.clone()
+}
+
+#[test]
+fn sessions_are_recorded_and_replay_headless() {
+    let mut app = test_app();
+    let replay = play_first_challenge(&mut app);
+    let tasks = game_state(&app).task_count();
+
+    assert_eq!(replay.entries.len(), 1 + tasks);
+    assert!(matches!(
+        replay.entries[0].action,
+        GameAction::StartChallenge(_)
+    ));
+    assert!(replay
+        .entries
+        .windows(2)
+        .all(|pair| pair[0].frame < pair[1].frame && pair[0].seconds <= pair[1].seconds));
+
+    let replayed = replay.run_headless(Game::default());
+    assert_eq!(replayed.answers(), game_state(&app).answers());
+    assert_eq!(replayed.score(), game_state(&app).score());
+}
+
+#[test]
+fn replay_files_play_back_on_screen() {
+    let mut app = test_app();
+    let replay = play_first_challenge(&mut app);
+    let path = std::env::temp_dir().join(format!("konnektoren-replay-{}.json", std::process::id()));
+    replay.save(&path).unwrap();
+    assert_eq!(Replay::load(&path).unwrap(), replay);
+
+    let mut replayed = test_app_with(KonnektorenPlugin::new().with_launch_options(LaunchOptions {
+        replay: Some(path.clone()),
+        ..Default::default()
+    }));
+    advance_until_state(&mut replayed, AppState::Game, 200);
+    for _ in 0..200 {
+        if game_state(&replayed).is_completed() {
+            break;
+        }
+        advance(&mut replayed, 1);
+    }
+    assert_eq!(game_state(&replayed).answers(), game_state(&app).answers());
+    // Seeded as the recorded session was.
+    let seed = replay.seed.unwrap();
+    assert_eq!(replayed.world.resource::<GameRng>().seed(), seed);
+    // Playing back does not record over a file.
+    assert_eq!(replayed.world.resource::<ReplayRecorder>().file, None);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn seed_from_the_command_line_is_used_and_recorded() {
+    let mut app = test_app_with(KonnektorenPlugin::new().with_launch_options(LaunchOptions {
+        seed: Some(42),
+        ..Default::default()
+    }));
+    let replay = play_first_challenge(&mut app);
+    assert_eq!(app.world.resource::<GameRng>().seed(), 42);
+    assert_eq!(replay.seed, Some(42));
+}
+
+#[test]
+fn playback_keeps_the_recorded_time_between_actions() {
+    let mut app = test_app();
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    // Two frames apart, as if nothing woke the event loop in between.
+    let entry = |frame, seconds, action| ReplayEntry {
+        frame,
+        seconds,
+        action,
+    };
+    app.insert_resource(ReplayPlayback::new(Replay {
+        entries: vec![
+            entry(100, 10.0, GameAction::StartChallenge(id)),
+            entry(102, 13.0, GameAction::SolveOption(0)),
+        ],
+        ..default()
+    }));
+    advance_until_state(&mut app, AppState::Game, 200);
+
+    advance_by(&mut app, Duration::from_secs(2));
+    assert!(game_state(&app).answers().is_empty());
+    // The event loop is kept running while actions are due.
+    assert!(!events::<RequestRedraw>(&app).is_empty());
+
+    advance_by(&mut app, Duration::from_secs(2));
+    assert_eq!(game_state(&app).answers().len(), 1);
+    assert!(app.world.resource::<ReplayPlayback>().is_finished());
+}
@@ -0,0 +1,823 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::{
+    input::gamepad::{
+        GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadInfo,
+    },
+    prelude::*,
+    window::{PrimaryWindow, RequestRedraw, WindowResized, WindowResolution},
+};
+use common::*;
+use konnektoren_core::challenges::{ChallengeResult, ChallengeType};
+use konnektoren_game::{
+    app_state::AppState,
+    button::Disabled,
+    deep_link::{CurrentRoute, Route},
+    game::{
+        events::{AnswerEvaluated, ChallengeCompleted, ChallengeStarted, GameAction, TaskAdvanced},
+        OptionNode,
+    },
+    map::ChallengeNode,
+    preload::Preload,
+    profile::Profile,
+    screen::{TransitionOverlay, TransitionTo},
+    settings::Settings,
+    slides::{SlideAction, SlideDot},
+    theme::{ColorVision, Palette, ThemeMode, UiTheme},
+};
+
+/// Path of the slide shown once any running transition has finished.
+fn slide_path(app: &mut App) -> Option<String> {
+    advance_by(app, Duration::from_secs(1));
+    shown_slide_path(app)
+}
+
+fn shown_slide_path(app: &mut App) -> Option<String> {
+    let handle = app
+        .world
+        .query_filtered::<&UiImage, Without<Button>>()
+        .single(&app.world)
+        .texture
+        .clone();
+    app.world
+        .resource::<AssetServer>()
+        .get_path(handle.id())
+        .map(|path| path.to_string())
+}
+
+/// Presses the node of the first challenge on the map, returning its id.
+fn press_first_challenge(app: &mut App) -> String {
+    advance_until_state(app, AppState::Slides, 100);
+    press_label(app, "Skip");
+    advance_until_state(app, AppState::Map, 20);
+
+    let id = game_state(app).game.game_path.challenges[0].id.clone();
+    press::<ChallengeNode>(app, |node| node.0 .1 == id);
+    id
+}
+
+fn start_first_challenge(app: &mut App) -> String {
+    let id = press_first_challenge(app);
+    advance_until_state(app, AppState::Game, 20);
+    id
+}
+
+/// Labels of the option buttons, in the order of the options.
+fn option_labels(app: &mut App) -> Vec<String> {
+    let mut options: Vec<(usize, Entity)> = app
+        .world
+        .query::<(&OptionNode, &Children)>()
+        .iter(&app.world)
+        .map(|(option, children)| (option.index, children[0]))
+        .collect();
+    options.sort();
+    options
+        .into_iter()
+        .map(|(_, label)| {
+            app.world.get::<Text>(label).unwrap().sections[0]
+                .value
+                .clone()
+        })
+        .collect()
+}
+
+/// Labels the option buttons of the current challenge should have.
+fn expected_option_labels(app: &App) -> Vec<String> {
+    let ChallengeType::MultipleChoice(ref dataset) = game_state(app).challenge.challenge_type;
+    dataset
+        .options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| format!("{}. {}", index + 1, option.name))
+        .collect()
+}
+
+#[test]
+fn starting_another_challenge_during_a_game_rebuilds_the_screen() {
+    let mut app = test_app();
+    start_first_challenge(&mut app);
+    advance(&mut app, 1);
+    let old_options: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, With<OptionNode>>()
+        .iter(&app.world)
+        .collect();
+    // Built once on entering, not again for the challenge that led there.
+    assert_eq!(old_options.len(), expected_option_labels(&app).len());
+
+    let second = game_state(&app).game.game_path.challenges[1].id.clone();
+    app.world
+        .send_event(GameAction::StartChallenge(second.clone()));
+    advance(&mut app, 3);
+    assert_eq!(app_state(&app), AppState::Game);
+    assert_eq!(game_state(&app).challenge.challenge_config.id, second);
+    assert_eq!(
+        app.world.resource::<CurrentRoute>().0,
+        Some(Route::Challenge(second))
+    );
+    assert!(old_options
+        .iter()
+        .all(|entity| app.world.get_entity(*entity).is_none()));
+    assert_eq!(option_labels(&mut app), expected_option_labels(&app));
+}
+
+/// Index into the option buttons of the option that answers the current task correctly.
+fn correct_option(app: &App) -> usize {
+    let game_state = game_state(app);
+    match game_state.challenge.challenge_type {
+        ChallengeType::MultipleChoice(ref dataset) => {
+            let question = &dataset.questions[game_state.current_task_index];
+            dataset
+                .options
+                .iter()
+                .position(|option| option.id == question.option)
+                .unwrap()
+        }
+    }
+}
+
+fn question_count(app: &App) -> usize {
+    match game_state(app).challenge.challenge_type {
+        ChallengeType::MultipleChoice(ref dataset) => dataset.questions.len(),
+    }
+}
+
+fn answered_count(app: &App) -> usize {
+    match game_state(app).challenge.challenge_result {
+        ChallengeResult::MultipleChoice(ref options) => options.len(),
+    }
+}
+
+#[test]
+fn splash_advances_to_slides() {
+    let mut app = test_app();
+    assert_eq!(app_state(&app), AppState::Splash);
+
+    advance_by(&mut app, Duration::from_millis(500));
+    assert_eq!(app_state(&app), AppState::Splash);
+
+    advance_by(&mut app, Duration::from_secs(1));
+    assert_eq!(app_state(&app), AppState::Slides);
+}
+
+#[test]
+fn splash_advances_again_when_reentered() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    app.world.send_event(TransitionTo(AppState::Splash));
+    advance_until_state(&mut app, AppState::Splash, 20);
+    // Leaving the intro counts as having seen it.
+    advance_until_state(&mut app, AppState::Map, 100);
+}
+
+#[test]
+fn splash_shows_assets_that_failed_to_load() {
+    let mut app = test_app();
+    let missing = app
+        .world
+        .resource::<AssetServer>()
+        .load::<Image>("missing.png");
+    app.world.resource_mut::<Preload>().add(missing);
+
+    advance_by(&mut app, Duration::from_secs(2));
+    assert_eq!(app_state(&app), AppState::Splash);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "Could not load missing.png"));
+
+    press_label(&mut app, "Continue");
+    advance_until_state(&mut app, AppState::Slides, 20);
+}
+
+#[test]
+fn slides_navigate_and_skip_to_map() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/1.png"));
+
+    press_label(&mut app, "Next");
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/3.png"));
+
+    press_label(&mut app, "Previous");
+    press_label(&mut app, "Previous");
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/9.png"));
+
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+}
+
+#[test]
+fn intro_is_skipped_once_completed_and_can_be_replayed() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert!(!app.world.resource::<Profile>().intro_completed);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert!(app.world.resource::<Profile>().intro_completed);
+
+    let mut app = test_app();
+    app.world.resource_mut::<Profile>().intro_completed = true;
+    advance_by(&mut app, Duration::from_secs(2));
+    assert_eq!(app_state(&app), AppState::Map);
+
+    press_label(&mut app, "Replay intro");
+    advance_until_state(&mut app, AppState::Slides, 20);
+}
+
+fn slide_opacity(app: &mut App) -> f32 {
+    app.world
+        .query_filtered::<&BackgroundColor, (With<UiImage>, Without<Button>)>()
+        .single(&app.world)
+        .0
+        .a()
+}
+
+#[test]
+fn slides_fade_between_slides() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert_eq!(slide_opacity(&mut app), 1.0);
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert!(slide_opacity(&mut app) < 1.0);
+
+    // A second press interrupts the running transition.
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/6m.png"));
+    assert_eq!(slide_opacity(&mut app), 1.0);
+}
+
+#[test]
+fn reduced_motion_swaps_slides_instantly() {
+    let mut app = test_app();
+    app.world.resource_mut::<Settings>().reduced_motion = true;
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(slide_opacity(&mut app), 1.0);
+    assert_eq!(shown_slide_path(&mut app).as_deref(), Some("slides/3.png"));
+}
+
+fn is_image_loading(app: &App, path: &'static str) -> bool {
+    app.world
+        .resource::<AssetServer>()
+        .get_handle::<Image>(path)
+        .is_some()
+}
+
+#[test]
+fn slides_preload_only_the_images_that_fit_the_window() {
+    let mut app = test_app();
+    assert!(is_image_loading(&app, "slides/6m.png"));
+    assert!(!is_image_loading(&app, "slides/6.png"));
+
+    let window = app
+        .world
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(1920.0, 1080.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.world.send_event(WindowResized {
+        window,
+        width: 1920.0,
+        height: 1080.0,
+    });
+    advance(&mut app, 1);
+    assert!(is_image_loading(&app, "slides/6.png"));
+}
+
+#[test]
+fn slides_navigate_with_keyboard() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/3.png"));
+
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/1.png"));
+
+    tap_key(&mut app, KeyCode::Escape);
+    advance_until_state(&mut app, AppState::Map, 20);
+}
+
+/// Presses and releases `button_type` on `gamepad`.
+fn tap_gamepad(app: &mut App, gamepad: Gamepad, button_type: GamepadButtonType) {
+    for value in [1.0, 0.0] {
+        app.world
+            .send_event(GamepadButtonChangedEvent::new(gamepad, button_type, value));
+        app.update();
+    }
+}
+
+#[test]
+fn slides_navigate_with_gamepad() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    advance(&mut app, 2);
+    let redraws = events::<RequestRedraw>(&app).len();
+    let gamepad = Gamepad::new(0);
+    app.world.send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected(GamepadInfo {
+            name: "Test pad".to_string(),
+        }),
+    ));
+    // Gamepads do not wake a reactive event loop, so the slides keep it running.
+    advance(&mut app, 2);
+    assert!(events::<RequestRedraw>(&app).len() > redraws);
+
+    tap_gamepad(&mut app, gamepad, GamepadButtonType::DPadRight);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/3.png"));
+    tap_gamepad(&mut app, gamepad, GamepadButtonType::LeftTrigger);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/1.png"));
+
+    tap_gamepad(&mut app, gamepad, GamepadButtonType::Start);
+    advance_until_state(&mut app, AppState::Map, 20);
+}
+
+/// Index of the highlighted dot of the slide indicator, and the number of dots.
+fn active_dot(app: &mut App) -> (Option<usize>, usize) {
+    let text = app.world.resource::<UiTheme>().palette.text;
+    let dots: Vec<(usize, Color)> = app
+        .world
+        .query::<(&SlideDot, &BackgroundColor)>()
+        .iter(&app.world)
+        .map(|(dot, background)| (dot.0, background.0))
+        .collect();
+    let active = dots
+        .iter()
+        .find(|(_, color)| *color == text)
+        .map(|(index, _)| *index);
+    (active, dots.len())
+}
+
+#[test]
+fn slide_dots_follow_the_current_slide() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    advance(&mut app, 1);
+    let (active, count) = active_dot(&mut app);
+    assert!(count > 1);
+    assert_eq!(active, Some(0));
+
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(active_dot(&mut app), (Some(1), count));
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(active_dot(&mut app), (Some(count - 1), count));
+}
+
+#[test]
+fn slides_show_captions_and_advance_automatically() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text.starts_with("Welcome to Konnektoren")));
+
+    advance_by(&mut app, Duration::from_secs(9));
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/3.png"));
+    assert!(!all_texts(&mut app)
+        .iter()
+        .any(|text| text.starts_with("Welcome to Konnektoren")));
+}
+
+#[test]
+fn map_node_starts_challenge() {
+    let mut app = test_app();
+    let id = press_first_challenge(&mut app);
+    assert_eq!(
+        events::<ChallengeStarted>(&app),
+        vec![ChallengeStarted {
+            challenge_id: id.clone()
+        }]
+    );
+    advance_until_state(&mut app, AppState::Game, 20);
+
+    let game_state = game_state(&app);
+    assert_eq!(game_state.challenge.challenge_config.id, id);
+    assert_eq!(game_state.current_task_index, 0);
+    assert!(all_texts(&mut app).iter().any(|text| text == "Task: 1"));
+}
+
+#[test]
+fn answering_all_tasks_completes_challenge() {
+    let mut app = test_app();
+    start_first_challenge(&mut app);
+
+    let questions = question_count(&app);
+    assert!(questions > 0);
+
+    for task in 0..questions {
+        let index = correct_option(&app);
+        press::<OptionNode>(&mut app, |option| option.index == index);
+        assert_eq!(answered_count(&app), task + 1);
+        let rows = all_texts(&mut app)
+            .into_iter()
+            .filter(|text| text.starts_with("Correct: "))
+            .count();
+        assert_eq!(rows, task + 1);
+        assert_eq!(
+            events::<AnswerEvaluated>(&app),
+            vec![AnswerEvaluated {
+                task_index: task,
+                correct: true
+            }]
+        );
+        if task + 1 < questions {
+            assert_eq!(
+                events::<TaskAdvanced>(&app),
+                vec![TaskAdvanced {
+                    task_index: task + 1
+                }]
+            );
+        }
+    }
+
+    let completed = events::<ChallengeCompleted>(&app);
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].score, 100.0);
+
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "You have completed the challenge with a score of 100.0!"));
+}
+
+#[test]
+fn leaving_game_despawns_screen() {
+    let mut app = test_app();
+    start_first_challenge(&mut app);
+    assert!(!texts_with::<Node>(&mut app).is_empty());
+
+    app.world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Map);
+    advance(&mut app, 2);
+
+    assert_eq!(app_state(&app), AppState::Map);
+    assert_eq!(
+        app.world
+            .query_filtered::<(), With<OptionNode>>()
+            .iter(&app.world)
+            .count(),
+        0
+    );
+}
+
+fn overlay_opacity(app: &mut App) -> f32 {
+    app.world
+        .query_filtered::<&BackgroundColor, With<TransitionOverlay>>()
+        .single(&app.world)
+        .0
+        .a()
+}
+
+#[test]
+fn screens_fade_through_transition_overlay() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    advance_by(&mut app, Duration::from_secs(1));
+    assert_eq!(overlay_opacity(&mut app), 0.0);
+
+    press_label(&mut app, "Skip");
+    assert_eq!(app_state(&app), AppState::Slides);
+    assert!(overlay_opacity(&mut app) > 0.0);
+
+    advance_until_state(&mut app, AppState::Map, 20);
+    advance_by(&mut app, Duration::from_secs(1));
+    assert_eq!(overlay_opacity(&mut app), 0.0);
+}
+
+#[test]
+fn reduced_motion_changes_screens_instantly() {
+    let mut app = test_app();
+    app.world.resource_mut::<Settings>().reduced_motion = true;
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    press_label(&mut app, "Skip");
+    assert_eq!(app_state(&app), AppState::Map);
+    assert_eq!(overlay_opacity(&mut app), 0.0);
+}
+
+#[test]
+fn reduced_motion_is_toggled_on_the_map_and_remembered() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert_eq!(app.world.resource::<Profile>().reduced_motion, None);
+
+    press_label(&mut app, "Reduce motion");
+    assert!(app.world.resource::<Settings>().reduced_motion);
+    assert_eq!(app.world.resource::<Profile>().reduced_motion, Some(true));
+
+    press_label(&mut app, "Full motion");
+    assert!(!app.world.resource::<Settings>().reduced_motion);
+    assert_eq!(app.world.resource::<Profile>().reduced_motion, Some(false));
+}
+
+#[test]
+fn theme_switches_at_runtime() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert_eq!(app.world.resource::<UiTheme>().mode, ThemeMode::Dark);
+
+    press_label(&mut app, "Light");
+    assert_eq!(app.world.resource::<UiTheme>().mode, ThemeMode::Light);
+    assert_eq!(app.world.resource::<ClearColor>().0, Palette::light().clear);
+    assert!(all_texts(&mut app).iter().any(|text| text == "Dark"));
+}
+
+#[test]
+fn disabled_button_ignores_presses() {
+    let mut app = test_app();
+    start_first_challenge(&mut app);
+
+    let option = app
+        .world
+        .query_filtered::<Entity, With<OptionNode>>()
+        .iter(&app.world)
+        .next()
+        .unwrap();
+    app.world.entity_mut(option).insert(Disabled);
+    press_entity(&mut app, option);
+
+    assert_eq!(answered_count(&app), 0);
+    assert_eq!(
+        app.world.get::<BackgroundColor>(option).unwrap().0,
+        app.world.resource::<UiTheme>().palette.button.disabled
+    );
+}
+
+#[test]
+fn language_switches_at_runtime() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    app.world.resource_mut::<Settings>().language = "de".to_string();
+    advance(&mut app, 1);
+    press_label(&mut app, "Überspringen");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "Intro wiederholen"));
+
+    press_label(&mut app, "Deutsch");
+    assert_eq!(app.world.resource::<Settings>().language, "uk");
+    assert!(all_texts(&mut app).iter().any(|text| text == "Українська"));
+    assert!(!all_texts(&mut app)
+        .iter()
+        .any(|text| text == "Intro wiederholen"));
+}
+
+/// Flex direction of the row holding the buttons that carry a `C`.
+fn button_row_direction<C: Component>(app: &mut App) -> FlexDirection {
+    let row = app
+        .world
+        .query_filtered::<&Parent, (With<C>, With<Button>)>()
+        .iter(&app.world)
+        .next()
+        .expect("no button row")
+        .get();
+    app.world.get::<Style>(row).unwrap().flex_direction
+}
+
+fn font_path(app: &App, font: &Handle<Font>) -> Option<String> {
+    app.world
+        .resource::<AssetServer>()
+        .get_path(font.id())
+        .map(|path| path.to_string())
+}
+
+#[test]
+fn right_to_left_languages_mirror_slides() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    assert_eq!(
+        button_row_direction::<SlideAction>(&mut app),
+        FlexDirection::Row
+    );
+
+    app.world.resource_mut::<Settings>().language = "ar".to_string();
+    advance(&mut app, 1);
+    assert_eq!(
+        button_row_direction::<SlideAction>(&mut app),
+        FlexDirection::RowReverse
+    );
+
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/3.png"));
+    tap_key(&mut app, KeyCode::ArrowRight);
+    assert_eq!(slide_path(&mut app).as_deref(), Some("slides/1.png"));
+}
+
+#[test]
+fn right_to_left_languages_mirror_options() {
+    let mut app = test_app();
+    app.world.resource_mut::<Settings>().language = "fa".to_string();
+    start_first_challenge(&mut app);
+    assert_eq!(
+        button_row_direction::<OptionNode>(&mut app),
+        FlexDirection::RowReverse
+    );
+}
+
+#[test]
+fn options_are_listed_in_portrait_windows() {
+    let mut app = test_app();
+    app.world.resource_mut::<Settings>().language = "fa".to_string();
+    let window = app
+        .world
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(400.0, 800.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    start_first_challenge(&mut app);
+    assert_eq!(
+        button_row_direction::<OptionNode>(&mut app),
+        FlexDirection::Column
+    );
+
+    app.world
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set(1280.0, 720.0);
+    app.world.send_event(WindowResized {
+        window,
+        width: 1280.0,
+        height: 720.0,
+    });
+    advance(&mut app, 1);
+    // Back to a grid of rows, mirrored for the right-to-left language.
+    assert_eq!(
+        button_row_direction::<OptionNode>(&mut app),
+        FlexDirection::RowReverse
+    );
+}
+
+#[test]
+fn fonts_follow_the_language() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    let font = app.world.resource::<UiTheme>().font.clone();
+    assert_eq!(
+        font_path(&app, &font).as_deref(),
+        Some("fonts/FiraSans-Bold.ttf")
+    );
+
+    app.world.resource_mut::<Settings>().language = "ar".to_string();
+    advance(&mut app, 1);
+    let font = app.world.resource::<UiTheme>().font.clone();
+    assert_eq!(
+        font_path(&app, &font).as_deref(),
+        Some("fonts/DejaVuSans-Bold.ttf")
+    );
+    let texts: Vec<Handle<Font>> = app
+        .world
+        .query::<&Text>()
+        .iter(&app.world)
+        .flat_map(|text| {
+            text.sections
+                .iter()
+                .map(|section| section.style.font.clone())
+        })
+        .collect();
+    assert!(!texts.is_empty());
+    assert!(texts.iter().all(|text_font| *text_font == font));
+}
+
+#[test]
+fn results_pair_colours_with_marks() {
+    let mut app = test_app();
+    start_first_challenge(&mut app);
+    assert!(question_count(&app) > 1);
+
+    let wrong = (correct_option(&app) + 1) % 2;
+    press::<OptionNode>(&mut app, |option| option.index == wrong);
+    let index = correct_option(&app);
+    press::<OptionNode>(&mut app, |option| option.index == index);
+
+    let texts = all_texts(&mut app);
+    assert_eq!(texts.iter().filter(|text| *text == "✗").count(), 1);
+    // One for the row, one next to the summary of the last answer.
+    assert_eq!(texts.iter().filter(|text| *text == "✓").count(), 2);
+}
+
+#[test]
+fn colour_vision_and_contrast_switch_at_runtime() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+
+    press_label(&mut app, "Colours: standard");
+    assert_eq!(
+        app.world.resource::<Settings>().color_vision,
+        ColorVision::RedGreen
+    );
+    let palette = Palette::new(ThemeMode::Dark, ColorVision::RedGreen, false);
+    assert_eq!(app.world.resource::<UiTheme>().palette, palette);
+    assert_ne!(palette.correct, Palette::dark().correct);
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| text == "Colours: red-green safe"));
+
+    press_label(&mut app, "High contrast");
+    assert!(app.world.resource::<Settings>().high_contrast);
+    assert_eq!(app.world.resource::<ClearColor>().0, Color::BLACK);
+    assert_eq!(
+        app.world.resource::<UiTheme>().palette.correct,
+        palette.correct
+    );
+}
+
+/// Whether the first text reading `value` is `size` pixels high.
+fn has_font_size(app: &mut App, value: &str, size: f32) -> bool {
+    app.world
+        .query::<&Text>()
+        .iter(&app.world)
+        .flat_map(|text| text.sections.iter())
+        .find(|section| section.value == value)
+        .is_some_and(|section| section.style.font_size == size)
+}
+
+#[test]
+fn text_sizes_return_exactly_after_a_full_cycle() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+
+    for percent in [100, 125, 150, 200] {
+        press_label(&mut app, &format!("Text size: {}%", percent));
+    }
+    assert!(has_font_size(&mut app, "Text size: 100%", 18.0));
+    assert!(has_font_size(&mut app, "Replay intro", 18.0));
+}
+
+#[test]
+fn ui_zooms_at_runtime() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert_eq!(app.world.resource::<UiScale>().0, 1.0);
+
+    press_label(&mut app, "Zoom: 100%");
+    assert_eq!(app.world.resource::<Settings>().ui_scale, 1.25);
+    assert_eq!(app.world.resource::<UiScale>().0, 1.25);
+    press_label(&mut app, "Zoom: 125%");
+    press_label(&mut app, "Zoom: 150%");
+    assert_eq!(app.world.resource::<UiScale>().0, 1.0);
+    assert!(all_texts(&mut app).contains(&"Zoom: 100%".to_string()));
+}
+
+#[test]
+fn text_scales_at_runtime() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    assert!(has_font_size(&mut app, "Text size: 100%", 18.0));
+
+    press_label(&mut app, "Text size: 100%");
+    press_label(&mut app, "Text size: 125%");
+    press_label(&mut app, "Text size: 150%");
+    assert_eq!(app.world.resource::<Settings>().text_scale, 2.0);
+    assert_eq!(app.world.resource::<UiTheme>().font_sizes.large, 40.0);
+    assert!(has_font_size(&mut app, "Text size: 200%", 36.0));
+    assert!(has_font_size(&mut app, "Replay intro", 36.0));
+
+    let id = game_state(&app).game.game_path.challenges[0].id.clone();
+    press::<ChallengeNode>(&mut app, |node| node.0 .1 == id);
+    advance_until_state(&mut app, AppState::Game, 20);
+    advance(&mut app, 1);
+    // Options grow with their text rather than clipping it.
+    let options: Vec<Style> = app
+        .world
+        .query_filtered::<&Style, With<OptionNode>>()
+        .iter(&app.world)
+        .cloned()
+        .collect();
+    assert!(!options.is_empty());
+    assert!(options
+        .iter()
+        .all(|style| style.width == Val::Auto && style.height == Val::Auto));
+    assert!(all_texts(&mut app)
+        .iter()
+        .any(|text| { text.starts_with("1. ") && has_font_size(&mut app, text, 36.0) }));
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use bevy::{
+    input::touch::{TouchInput, TouchPhase},
+    prelude::*,
+    window::RequestRedraw,
+};
+use common::*;
+use konnektoren_game::{
+    app_state::AppState,
+    map::{ChallengeNode, MapView},
+    slides::SlideAction,
+    theme::MIN_TARGET_SIZE,
+    touch::{Gesture, Pointer},
+};
+
+fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2) {
+    app.world.send_event(TouchInput {
+        phase,
+        position,
+        window: Entity::PLACEHOLDER,
+        force: None,
+        id,
+    });
+}
+
+fn gestures(app: &App) -> Vec<Gesture> {
+    events::<Gesture>(app)
+}
+
+fn open_map(app: &mut App) {
+    advance_until_state(app, AppState::Slides, 100);
+    press_label(app, "Skip");
+    advance_until_state(app, AppState::Map, 20);
+}
+
+/// The node of the challenge named by [`first_challenge_name`].
+fn first_challenge_node(app: &mut App) -> Entity {
+    let id = game_state(app).game.game_path.challenges[0].id.clone();
+    app.world
+        .query::<(Entity, &ChallengeNode)>()
+        .iter(&app.world)
+        .find(|(_, node)| node.0 .1 == id)
+        .map(|(entity, _)| entity)
+        .unwrap()
+}
+
+/// Puts a finger down on the challenge `node`, as the UI would on a touch screen.
+fn touch_node(app: &mut App, node: Entity) {
+    touch(app, 0, TouchPhase::Started, Vec2::new(100.0, 100.0));
+    *app.world.get_mut::<Interaction>(node).unwrap() = Interaction::Pressed;
+    advance(app, 1);
+}
+
+/// Lifts the finger put down by [`touch_node`].
+fn release_node(app: &mut App, node: Entity) {
+    touch(app, 0, TouchPhase::Ended, Vec2::new(100.0, 100.0));
+    *app.world.get_mut::<Interaction>(node).unwrap() = Interaction::None;
+    advance(app, 1);
+}
+
+fn challenge_details(app: &mut App) -> Option<String> {
+    all_texts(app)
+        .into_iter()
+        .find(|text| text.starts_with(&format!("{}: ", first_challenge_name(app))))
+}
+
+fn first_challenge_name(app: &App) -> String {
+    game_state(app).game.game_path.challenges[0].name.clone()
+}
+
+#[test]
+fn touches_become_taps_long_presses_and_swipes() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+
+    touch(&mut app, 0, TouchPhase::Started, Vec2::new(100.0, 100.0));
+    advance(&mut app, 1);
+    touch(&mut app, 0, TouchPhase::Moved, Vec2::new(102.0, 101.0));
+    touch(&mut app, 0, TouchPhase::Ended, Vec2::new(102.0, 101.0));
+    advance(&mut app, 1);
+    assert_eq!(*app.world.resource::<Pointer>(), Pointer::Touch);
+    assert!(gestures(&app).contains(&Gesture::Tap(Vec2::new(102.0, 101.0))));
+
+    touch(&mut app, 1, TouchPhase::Started, Vec2::new(100.0, 100.0));
+    advance(&mut app, 12);
+    assert!(gestures(&app).contains(&Gesture::LongPress(Vec2::new(100.0, 100.0))));
+    touch(&mut app, 1, TouchPhase::Ended, Vec2::new(100.0, 100.0));
+    advance(&mut app, 1);
+    assert!(!gestures(&app)
+        .iter()
+        .any(|gesture| matches!(gesture, Gesture::Tap(_))));
+
+    touch(&mut app, 2, TouchPhase::Started, Vec2::new(300.0, 100.0));
+    advance(&mut app, 1);
+    touch(&mut app, 2, TouchPhase::Moved, Vec2::new(100.0, 110.0));
+    touch(&mut app, 2, TouchPhase::Ended, Vec2::new(100.0, 110.0));
+    advance(&mut app, 1);
+    assert!(gestures(&app).contains(&Gesture::Swipe(Vec2::new(-200.0, 10.0))));
+}
+
+#[test]
+fn taps_start_challenges_and_long_presses_show_details() {
+    let mut app = test_app();
+    open_map(&mut app);
+    let node = first_challenge_node(&mut app);
+    assert_eq!(challenge_details(&mut app), None);
+
+    touch_node(&mut app, node);
+    assert_eq!(app_state(&app), AppState::Map);
+    advance(&mut app, 12);
+    assert!(challenge_details(&mut app).is_some());
+    release_node(&mut app, node);
+    advance(&mut app, 2);
+    assert_eq!(app_state(&app), AppState::Map);
+
+    touch_node(&mut app, node);
+    release_node(&mut app, node);
+    advance_until_state(&mut app, AppState::Game, 20);
+}
+
+#[test]
+fn held_touches_keep_updating_until_they_become_a_long_press() {
+    let mut app = test_app();
+    open_map(&mut app);
+    advance(&mut app, 20);
+    assert!(events::<RequestRedraw>(&app).is_empty());
+
+    touch(&mut app, 0, TouchPhase::Started, Vec2::new(100.0, 100.0));
+    advance(&mut app, 1);
+    assert!(!events::<RequestRedraw>(&app).is_empty());
+    advance(&mut app, 11);
+    assert!(gestures(&app).contains(&Gesture::LongPress(Vec2::new(100.0, 100.0))));
+    advance(&mut app, 2);
+    assert!(events::<RequestRedraw>(&app).is_empty());
+}
+
+#[test]
+fn pinching_zooms_the_map() {
+    let mut app = test_app();
+    open_map(&mut app);
+    let node = first_challenge_node(&mut app);
+    let width = app.world.get::<Style>(node).unwrap().width;
+
+    touch(&mut app, 0, TouchPhase::Started, Vec2::new(300.0, 300.0));
+    touch(&mut app, 1, TouchPhase::Started, Vec2::new(400.0, 300.0));
+    advance(&mut app, 1);
+    touch(&mut app, 0, TouchPhase::Moved, Vec2::new(250.0, 300.0));
+    touch(&mut app, 1, TouchPhase::Moved, Vec2::new(450.0, 300.0));
+    advance(&mut app, 1);
+    assert_eq!(app.world.resource::<MapView>().zoom, 2.0);
+    let Val::Px(before) = width else {
+        panic!("map nodes are sized in pixels");
+    };
+    assert_eq!(
+        app.world.get::<Style>(node).unwrap().width,
+        Val::Px(before * 2.0)
+    );
+
+    touch(&mut app, 0, TouchPhase::Ended, Vec2::new(250.0, 300.0));
+    touch(&mut app, 1, TouchPhase::Ended, Vec2::new(450.0, 300.0));
+    advance(&mut app, 1);
+    assert_eq!(app_state(&app), AppState::Map);
+}
+
+/// Smallest width and height each button can shrink to, in pixels.
+fn button_minimums(app: &mut App) -> Vec<(f32, f32)> {
+    let px = |values: [Val; 2]| {
+        values
+            .iter()
+            .filter_map(|value| match value {
+                Val::Px(px) => Some(*px),
+                _ => None,
+            })
+            .fold(0.0, f32::max)
+    };
+    app.world
+        .query_filtered::<&Style, With<Button>>()
+        .iter(&app.world)
+        .map(|style| {
+            (
+                px([style.width, style.min_width]),
+                px([style.height, style.min_height]),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn buttons_are_large_enough_to_touch() {
+    let mut app = test_app();
+    advance_until_state(&mut app, AppState::Slides, 100);
+    advance(&mut app, 1);
+    assert!(!app
+        .world
+        .query::<&SlideAction>()
+        .iter(&app.world)
+        .collect::<Vec<_>>()
+        .is_empty());
+    let slides = button_minimums(&mut app);
+
+    press_label(&mut app, "Skip");
+    advance_until_state(&mut app, AppState::Map, 20);
+    let map = button_minimums(&mut app);
+
+    for (width, height) in slides.into_iter().chain(map) {
+        assert!(width >= MIN_TARGET_SIZE && height >= MIN_TARGET_SIZE);
+    }
+}